@@ -0,0 +1,202 @@
+//! The answer of a puzzle part: a number, a string or letters drawn as pixels.
+use std::fmt::Display;
+
+use crate::grid::Grid;
+use crate::ocr;
+
+/// The runner reads the displayed result of a solution into an answer with [`Answer::parse`], so parts can
+/// return any `Display` type. Drawings of a `Grid<bool>` are returned as `Answer::from(grid)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Letters drawn by the `true` cells, with the text read from them if every letter was recognised.
+    Pixels {
+        grid: Grid<bool>,
+        text: Option<String>,
+    },
+}
+
+impl Answer {
+    /// Reads the letters of a drawing with [`ocr::recognize`].
+    pub fn pixels(grid: Grid<bool>) -> Self {
+        let text = ocr::recognize(&grid);
+        Self::Pixels { grid, text }
+    }
+
+    /// Reads a displayed result: a number, letters drawn with `#` or `█` on `.` or ` `, or any other text.
+    pub fn parse(text: &str) -> Self {
+        if let Ok(n) = text.parse::<i128>() {
+            // keep e.g. leading zeros, which a number would drop.
+            if n.to_string() == text {
+                return Self::Number(n);
+            }
+        }
+
+        let drawing = text.trim_matches(['\n', '\r']);
+        let width = drawing
+            .lines()
+            .next()
+            .map_or(0, |line| line.chars().count());
+        let is_drawing = drawing.lines().count() > 1
+            && drawing.contains(['#', '█'])
+            && drawing.lines().all(|line| {
+                line.chars().count() == width
+                    && line.chars().all(|ch| matches!(ch, '#' | '█' | '.' | ' '))
+            });
+
+        if is_drawing {
+            Self::pixels(Grid::parse(drawing, |ch| matches!(ch, '#' | '█')))
+        } else {
+            Self::Text(text.to_string())
+        }
+    }
+
+    /// The value that is submitted and stored, `None` if the letters of a drawing could not be read.
+    pub fn submission(&self) -> Option<String> {
+        match self {
+            Self::Number(n) => Some(n.to_string()),
+            Self::Text(text) => Some(text.clone()),
+            Self::Pixels { text, .. } => text.clone(),
+        }
+    }
+
+    /// The pixels of a drawing, with `█` for lit cells.
+    pub fn drawing(&self) -> Option<String> {
+        let Self::Pixels { grid, .. } = self else {
+            return None;
+        };
+
+        let rows: Vec<String> = grid
+            .rows()
+            .map(|row| row.iter().map(|on| if *on { '█' } else { ' ' }).collect())
+            .collect();
+        Some(rows.join("\n"))
+    }
+}
+
+/// Numbers and text display as they are, drawings as their pixels. An answer that a solution returns
+/// is therefore read back into the same answer by [`Answer::parse`].
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Pixels { .. } => write!(f, "{}", self.drawing().unwrap_or_default()),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Number(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Number(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Number(value as i128)
+    }
+}
+
+/// Numbers above `i128::MAX` are kept as text.
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Self::Text(value.to_string()), Self::Number)
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<Grid<bool>> for Answer {
+    fn from(value: Grid<bool>) -> Self {
+        Self::pixels(value)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+    use crate::grid::Grid;
+
+    #[test]
+    fn converts_numbers_and_text() {
+        assert_eq!(Answer::from(42u32), Answer::Number(42));
+        assert_eq!(Answer::from(-3i64).submission(), Some("-3".to_string()));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(
+            Answer::from("4,6,3").submission(),
+            Some("4,6,3".to_string())
+        );
+        assert_eq!(Answer::from(7usize).drawing(), None);
+        assert_eq!(
+            Answer::from(u128::MAX).submission(),
+            Some(u128::MAX.to_string())
+        );
+        assert_eq!(Answer::from('x'), Answer::Text("x".into()));
+    }
+
+    #[test]
+    fn parses_displayed_results() {
+        assert_eq!(Answer::parse("-42"), Answer::Number(-42));
+        assert_eq!(Answer::parse("0042"), Answer::Text("0042".into()));
+        assert_eq!(
+            Answer::parse(&u128::MAX.to_string()),
+            Answer::Text(u128::MAX.to_string())
+        );
+        assert_eq!(Answer::parse("4,6,3"), Answer::Text("4,6,3".into()));
+        assert_eq!(
+            Answer::parse("#..#.\n#..#.\n####.\n#..#.\n#..#.\n#..#.\n").submission(),
+            Some("H".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_drawn_letters() {
+        let grid = Grid::parse("#..#.\n#..#.\n####.\n#..#.\n#..#.\n#..#.", |ch| ch == '#');
+        let answer = Answer::from(grid);
+        assert_eq!(answer.submission(), Some("H".to_string()));
+        assert!(answer.drawing().unwrap().starts_with("█  █ \n"));
+        assert_eq!(Answer::parse(&answer.to_string()), answer);
+    }
+
+    #[test]
+    fn shows_unreadable_drawings() {
+        let answer = Answer::from(Grid::parse("#.\n.#", |ch| ch == '#'));
+        assert_eq!(answer.submission(), None);
+        assert_eq!(answer.to_string(), "█ \n █");
+        assert_eq!(Answer::parse(&answer.to_string()), answer);
+    }
+}
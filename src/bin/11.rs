@@ -1,7 +1,6 @@
 advent_of_code::solution!(11);
 
-use anyhow::{bail, Result};
-use cached::proc_macro::cached;
+use std::collections::HashMap;
 use std::num::ParseIntError;
 
 pub fn parse(input: &str) -> Vec<u64> {
@@ -14,39 +13,53 @@ pub fn parse(input: &str) -> Vec<u64> {
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let res = parse(input).into_iter().map(|n| blink(n, 25)).sum();
-    Some(res)
+    Some(blink(&parse(input), 25))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let res = parse(input).into_iter().map(|n| blink(n, 75)).sum();
-    Some(res)
+    Some(blink(&parse(input), 75))
 }
 
-#[cached]
-fn blink(n: u64, depth: u64) -> u64 {
-    if depth == 0 {
-        return 1;
+/// Counts the stones after blinking `times` times.
+///
+/// The order of the stones does not matter, so only the number of stones with each value is tracked.
+/// The map is local to the call, which keeps every benchmark sample doing the full work.
+fn blink(stones: &[u64], times: usize) -> u64 {
+    let mut counts: HashMap<u64, u64> = HashMap::new();
+    for stone in stones {
+        *counts.entry(*stone).or_default() += 1;
     }
 
-    if n == 0 {
-        return blink(1, depth - 1);
-    }
-    match split_num(n) {
-        Ok((l, r)) => blink(l, depth - 1) + blink(r, depth - 1),
-        Err(_) => blink(n * 2024, depth - 1),
+    let mut next = HashMap::with_capacity(counts.len());
+    for _ in 0..times {
+        for (&stone, &count) in &counts {
+            match stone {
+                0 => *next.entry(1).or_default() += count,
+                _ => match split_num(stone) {
+                    Some((l, r)) => {
+                        *next.entry(l).or_default() += count;
+                        *next.entry(r).or_default() += count;
+                    }
+                    None => *next.entry(stone * 2024).or_default() += count,
+                },
+            }
+        }
+        std::mem::swap(&mut counts, &mut next);
+        next.clear();
     }
+
+    counts.values().sum()
 }
 
-fn split_num(n: u64) -> Result<(u64, u64)> {
-    let mut s = n.to_string();
-    if s.len() % 2 != 0 {
-        bail!("not symmetric")
+/// Splits a number with an even number of digits into its left and right half.
+fn split_num(n: u64) -> Option<(u64, u64)> {
+    let digits = n.checked_ilog10()? + 1;
+    if digits % 2 == 1 {
+        return None;
     }
 
-    let tail = s.split_off(s.len() / 2);
-
-    Ok((s.parse()?, tail.parse()?))
+    let divisor = 10u64.pow(digits / 2);
+    Some((n / divisor, n % divisor))
 }
 
 #[cfg(test)]
@@ -64,4 +77,13 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(65601038650482));
     }
+
+    #[test]
+    fn test_split_num() {
+        assert_eq!(split_num(1000), Some((10, 0)));
+        assert_eq!(split_num(253000), Some((253, 0)));
+        assert_eq!(split_num(17), Some((1, 7)));
+        assert_eq!(split_num(0), None);
+        assert_eq!(split_num(999), None);
+    }
 }
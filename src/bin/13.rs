@@ -1,4 +1,4 @@
-use itertools::*;
+use advent_of_code::{input::Input, linear::min_cost_solution};
 
 advent_of_code::solution!(13);
 
@@ -11,47 +11,24 @@ struct ClawMachine {
     pub prize: Coord,
 }
 
-fn parse(input: &str) -> Vec<ClawMachine> {
-    let chunked: Vec<_> = input
-        .split("\n")
-        .filter(|s| !s.is_empty())
-        .chunks(3)
-        .into_iter()
-        .map(|mut raw| {
-            let button_a = raw.next().unwrap();
-            let button_b = raw.next().unwrap();
-            let prize = raw.next().unwrap();
-
-            let btn_ax = button_a[12..14].parse().unwrap();
-            let btn_ay = button_a[18..20].parse().unwrap();
-            let btn_a: Coord = (btn_ax, btn_ay);
-
-            let btn_b: Coord = (
-                button_b[12..14].parse().unwrap(),
-                button_b[18..20].parse().unwrap(),
-            );
-
-            let xeq = prize.find("X=").unwrap();
-            let comma = prize.find(",").unwrap();
-            let yeq = prize.find("Y=").unwrap();
-
-            let prize: Coord = (
-                prize[xeq + 2..comma].parse().unwrap(),
-                prize[yeq + 2..].parse().unwrap(),
-            );
+fn parse(input: Input) -> Vec<ClawMachine> {
+    input
+        .blocks()
+        .map(|block| {
+            let [ax, ay, bx, by, px, py] = block.ints().collect::<Vec<_>>()[..] else {
+                panic!("expected six numbers per claw machine, got \"{block}\"");
+            };
 
             ClawMachine {
-                btn_a,
-                btn_b,
-                prize,
+                btn_a: (ax, ay),
+                btn_b: (bx, by),
+                prize: (px, py),
             }
         })
-        .collect();
-
-    chunked
+        .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: Input) -> Option<u32> {
     let input = parse(input);
 
     let mut sum = 0;
@@ -70,30 +47,13 @@ fn push_buttons(claw: ClawMachine) -> i64 {
         prize,
     } = claw;
 
-    let (x1, y1) = (btn_a.0 as f64, btn_a.1 as f64);
-    let (x2, y2) = (btn_b.0 as f64, btn_b.1 as f64);
-    let (px, py) = (prize.0 as f64, prize.1 as f64);
-
-    let b = (py * x1 - y1 * px) / (y2 * x1 - y1 * x2);
-
-    let a = (px - b * x2) / x1;
-
-    let sol = [a, b];
-
-    for s0 in [sol[0].ceil(), sol[0].floor()] {
-        for s1 in [sol[1].ceil(), sol[1].floor()] {
-            let eq1_ok = x1 * s0 + x2 * s1 == px;
-            let eq2_ok = y1 * s0 + y2 * s1 == py;
-            if eq1_ok && eq2_ok {
-                return (sol[0] * 3f64 + sol[1]) as i64;
-            }
-        }
-    }
+    let m = [[btn_a.0, btn_b.0], [btn_a.1, btn_b.1]].map(|row| row.map(i128::from));
+    let rhs = [prize.0, prize.1].map(i128::from);
 
-    0
+    min_cost_solution(m, rhs, [3, 1]).map_or(0, |[a, b]| (3 * a + b) as i64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: Input) -> Option<u64> {
     let input = parse(input);
 
     let input = input.into_iter().map(|mut el| {
@@ -117,13 +77,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(Input::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(Input::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(875318608908));
     }
 }
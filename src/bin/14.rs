@@ -1,26 +1,23 @@
 use std::collections::HashMap;
+use std::path::Path;
 
-advent_of_code::solution!(14);
+use advent_of_code::{
+    grid::Grid, image::write_image, input::Input, linear::crt, template::solution_arg,
+};
+
+advent_of_code::solution!(14, extras = write_tree_image);
 
 type Coords = (i64, i64);
 
-fn parse(input: &str) -> Vec<(Coords, Coords)> {
+fn parse(input: Input) -> Vec<(Coords, Coords)> {
     input
-        .trim()
-        .split("\n")
+        .lines()
         .map(|ln| {
-            let parts = ln.split(" ").collect::<Vec<_>>();
-            let pos: Vec<i64> = parts[0][2..]
-                .split(",")
-                .map(|el| el.parse().unwrap())
-                .collect();
-
-            let vels: Vec<i64> = parts[1][2..]
-                .split(",")
-                .map(|el| el.parse().unwrap())
-                .collect();
-
-            ((pos[1], pos[0]), (vels[1], vels[0]))
+            let [x, y, vx, vy] = ln.ints().collect::<Vec<_>>()[..] else {
+                panic!("expected a position and a velocity, got \"{ln}\"");
+            };
+
+            ((y, x), (vy, vx))
         })
         .collect()
 }
@@ -30,7 +27,7 @@ const WIDTH: i64 = 101;
 // const HEIGHT: i64 = 7;
 const HEIGHT: i64 = 103;
 const SECONDS: i64 = 100;
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: Input) -> Option<u64> {
     let input = parse(input);
 
     let safety_rating = input
@@ -82,49 +79,71 @@ fn add_with_custom_overflow(n: i64, d: i64, overflow: i64, seconds: i64) -> i64
     (res % overflow + overflow) % overflow
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+/// The offset in `0..period` at which the coordinates are the least spread out.
+fn min_variance_offset(robots: &[(i64, i64)], period: i64) -> i64 {
+    let n = robots.len() as i64;
+    (0..period)
+        .min_by_key(|seconds| {
+            let (sum, sum_sq) = robots.iter().fold((0, 0), |(sum, sum_sq), (pos, vel)| {
+                let pos = add_with_custom_overflow(*pos, *vel, period, *seconds);
+                (sum + pos, sum_sq + pos * pos)
+            });
+            // the variance, scaled by n^2
+            n * sum_sq - sum * sum
+        })
+        .unwrap()
+}
+
+/// The rows and columns repeat every `HEIGHT` and `WIDTH` seconds respectively. The tree is the moment
+/// at which the robots cluster, so the row and column offsets with the smallest variance are found
+/// independently and combined with the Chinese Remainder Theorem.
+pub fn part_two(input: Input) -> Option<u64> {
     let input = parse(input);
-    let mut min_sr = u64::MAX;
-    let mut min_sr_sec = 0;
-
-    for ii in 0..WIDTH * HEIGHT {
-        let safety_rating: u64 = input
-            .clone()
-            .into_iter()
-            .map(|(coords, velocity)| {
-                let (y, x) = compute_new_coords(coords, velocity, ii);
-
-                get_quad((y, x), WIDTH, HEIGHT)
-            })
-            .fold(HashMap::new(), |mut acc, n| {
-                if n != 0 {
-                    acc.entry(n)
-                        .and_modify(|cnt: &mut u64| *cnt += 1)
-                        .or_insert(1);
-                }
-                acc
-            })
-            .values()
-            .product();
-
-        if safety_rating < min_sr {
-            min_sr = safety_rating;
-            min_sr_sec = ii;
-        }
+
+    let rows: Vec<_> = input.iter().map(|((y, _), (vy, _))| (*y, *vy)).collect();
+    let cols: Vec<_> = input.iter().map(|((_, x), (_, vx))| (*x, *vx)).collect();
+
+    let (seconds, _) = crt(&[
+        (
+            i128::from(min_variance_offset(&rows, HEIGHT)),
+            i128::from(HEIGHT),
+        ),
+        (
+            i128::from(min_variance_offset(&cols, WIDTH)),
+            i128::from(WIDTH),
+        ),
+    ])?;
+
+    Some(seconds as u64)
+}
+
+/// Writes the tree of part two to the file passed with `--image`, e.g. `cargo solve 14 -- --image tree.ppm`.
+fn write_tree_image(input: &str) {
+    let Some(path) = solution_arg("--image") else {
+        return;
+    };
+    let Some(path) = path else {
+        eprintln!("`--image` expects the path of the image to write.");
+        return;
+    };
+    let Some(seconds) = part_two(Input::new(input)) else {
+        return;
+    };
+
+    write_frame(&parse(Input::new(input)), seconds as i64, Path::new(&path));
+}
+
+fn write_frame(robots: &[(Coords, Coords)], seconds: i64, path: &Path) {
+    let mut frame = Grid::new(WIDTH as usize, HEIGHT as usize, false);
+    for (coords, velocity) in robots {
+        let (y, x) = compute_new_coords(*coords, *velocity, seconds);
+        frame[(y as usize, x as usize)] = true;
+    }
+
+    match write_image(path, &frame, [0, 153, 0], [15, 15, 35]) {
+        Ok(()) => eprintln!("Wrote frame {seconds} to {}", path.display()),
+        Err(e) => eprintln!("Failed to write image to {}: {e}", path.display()),
     }
-    // This will print the result to the console
-    //
-    // let mut grid = [[" "; WIDTH as usize]; HEIGHT as usize];
-    // for (coords, velocity) in input {
-    //     let (y, x) = compute_new_coords(coords, velocity, min_sr_sec);
-    //     let (y, x) = (y as usize, x as usize);
-    //     grid[y][x] = "█"
-    // }
-
-    // grid.into_iter()
-    //     .map(|arr| arr.join(""))
-    //     .for_each(|row| eprintln!("{}", row));
-    Some(min_sr_sec as u64)
 }
 
 fn compute_new_coords(coords: (i64, i64), velocity: (i64, i64), seconds: i64) -> (i64, i64) {
@@ -150,13 +169,42 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(Input::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(21));
     }
 
+    /// Robots that form a 10x10 block at `seconds`, mixed with some that never do.
+    fn clustered_robots(seconds: i64) -> String {
+        let mut state = 17_i64;
+        let mut random = |m: i64| {
+            state = (state * 1_103_515_245 + 12_345) % 2_147_483_648;
+            state % m
+        };
+
+        let mut lines = vec![];
+        for i in 0..130 {
+            let (vx, vy) = (random(201) - 100, random(207) - 103);
+            let (x, y) = if i < 100 {
+                let (tx, ty) = (40 + i % 10, 50 + i / 10);
+                (
+                    add_with_custom_overflow(tx, -vx, WIDTH, seconds),
+                    add_with_custom_overflow(ty, -vy, HEIGHT, seconds),
+                )
+            } else {
+                (random(WIDTH), random(HEIGHT))
+            };
+            lines.push(format!("p={x},{y} v={vx},{vy}"));
+        }
+        lines.join("\n")
+    }
+
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2549));
+        for seconds in [1, 2549, 7_777, WIDTH * HEIGHT - 1] {
+            let input = clustered_robots(seconds);
+            assert_eq!(part_two(Input::new(&input)), Some(seconds as u64));
+        }
     }
 }
@@ -1,8 +1,29 @@
-use std::{collections::BTreeSet, fmt::Display};
-
-use itertools::Itertools;
-
-advent_of_code::solution!(15);
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Write as _},
+    io::{self, IsTerminal, Read, Write},
+    process::{Command, Stdio},
+};
+
+use advent_of_code::{
+    grid::Grid as Cells,
+    image::Rgb,
+    input::Input,
+    point::{Direction, Point},
+    template::{recorder, solution_flag},
+};
+
+advent_of_code::solution!(15, extras = play_from_args);
+
+/// Colours of the tiles of both parts when recording with `--record`.
+fn colour(tile: char) -> Rgb {
+    match tile {
+        '#' => [110, 110, 120],
+        'O' | '[' | ']' => [214, 150, 50],
+        '@' => [230, 60, 60],
+        _ => [20, 20, 30],
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 enum Tile {
@@ -37,98 +58,38 @@ impl Display for Tile {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    const fn get_coord(&self) -> (isize, isize) {
-        match &self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        }
-    }
-
-    const fn opposite(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Down,
-            Direction::Down => Direction::Up,
-            Direction::Left => Direction::Right,
-            Direction::Right => Direction::Left,
-        }
-    }
-}
-
-const fn add_coords((a_r, a_c): (isize, isize), (b_r, b_c): (isize, isize)) -> (isize, isize) {
-    (a_r + b_r, a_c + b_c)
-}
-
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
-        match value {
-            '^' => Direction::Up,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            '>' => Direction::Right,
-            _ => unimplemented!(),
-        }
-    }
-}
-
+#[derive(Clone)]
 struct Grid {
-    elements: Vec<Vec<Tile>>,
-    robot_location: (isize, isize),
+    elements: Cells<Tile>,
+    robot_location: Point,
 }
 
 impl Grid {
-    #[allow(dead_code)]
-    fn print_grid(&self) {
-        eprintln!("robots position {:?}", self.robot_location);
-        for row in &self.elements {
-            for col in row {
-                eprint!("{col}");
-            }
-            eprintln!();
-        }
-    }
-
-    fn get_at(&self, (row, col): (isize, isize)) -> Tile {
-        self.elements[row as usize][col as usize]
+    fn get_at(&self, pos: Point) -> Tile {
+        self.elements[pos]
     }
 
-    fn update_at(&mut self, (row, col): (isize, isize), value: Tile) {
-        self.elements[row as usize][col as usize] = value
+    fn update_at(&mut self, pos: Point, value: Tile) {
+        self.elements[pos] = value
     }
 }
 
-fn parse(input: &str) -> (Grid, Vec<Direction>) {
-    let parts = input.split("\n\n").collect::<Vec<_>>();
-    let directions: Vec<Direction> = parts[1]
-        .replace("\n", "")
-        .chars()
-        .map(Direction::from)
-        .collect();
+fn find_robot<T: PartialEq>(elements: &Cells<T>, robot: T) -> Point {
+    elements.find(|el| *el == robot).unwrap().into()
+}
 
-    let grid: Vec<Vec<Tile>> = parts[0]
-        .split("\n")
-        .map(|row| row.chars().map(Tile::from).collect())
+fn parse(input: Input) -> (Grid, Vec<Direction>) {
+    let [grid, moves] = input.blocks().collect::<Vec<_>>()[..] else {
+        panic!("expected the warehouse and the moves separated by a blank line");
+    };
+    let directions: Vec<Direction> = moves
+        .lines()
+        .flat_map(|line| line.as_str().chars())
+        .map(|ch| Direction::try_from(ch).unwrap())
         .collect();
 
-    let robot_location = grid
-        .iter()
-        .enumerate()
-        .find_map(|(row_id, r)| {
-            r.iter()
-                .find_position(|el| **el == Tile::Robot)
-                .map(|(col_id, _)| (row_id as isize, col_id as isize))
-        })
-        .unwrap();
+    let grid = grid.grid(Tile::from);
+    let robot_location = find_robot(&grid, Tile::Robot);
 
     (
         Grid {
@@ -139,14 +100,14 @@ fn parse(input: &str) -> (Grid, Vec<Direction>) {
     )
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: Input) -> Option<u64> {
     let (mut grid, dirs) = parse(input);
-    // grid.print_grid();
+    recorder::push_frame(&grid.elements, colour);
 
     for dir in dirs {
         move_robot_in_dir(&mut grid, dir);
+        recorder::push_frame(&grid.elements, colour);
     }
-    // grid.print_grid();
     Some(calc_gps_coords(&grid))
 }
 
@@ -156,7 +117,7 @@ fn move_robot_in_dir(grid: &mut Grid, dir: Direction) {
     let mut cur_pos = grid.robot_location;
 
     while no_wall && !has_space {
-        cur_pos = add_coords(cur_pos, dir.get_coord());
+        cur_pos = cur_pos + dir.offset();
         let tile = grid.get_at(cur_pos);
         match tile {
             Tile::Wall => no_wall = false,
@@ -171,7 +132,7 @@ fn move_robot_in_dir(grid: &mut Grid, dir: Direction) {
             let mut robot_reached = false;
 
             while !robot_reached {
-                let prev_pos = add_coords(cur_pos, dir.opposite().get_coord());
+                let prev_pos = cur_pos + dir.opposite().offset();
                 let tile = grid.get_at(prev_pos);
                 grid.update_at(cur_pos, tile);
                 if tile == Tile::Robot {
@@ -193,14 +154,11 @@ fn move_robot_in_dir(grid: &mut Grid, dir: Direction) {
 fn calc_gps_coords(grid: &Grid) -> u64 {
     grid.elements
         .iter()
-        .enumerate()
-        .flat_map(|(row_idx, row)| {
-            row.iter().enumerate().filter_map(move |(col_idx, tile)| {
-                if *tile == Tile::Box {
-                    return Some(100 * row_idx as u64 + col_idx as u64);
-                }
-                None
-            })
+        .filter_map(|((row_idx, col_idx), tile)| {
+            if *tile == Tile::Box {
+                return Some(100 * row_idx as u64 + col_idx as u64);
+            }
+            None
         })
         .sum()
 }
@@ -228,35 +186,26 @@ impl Display for FatTile {
     }
 }
 
+#[derive(Clone)]
 struct FatGrid {
-    elements: Vec<Vec<FatTile>>,
-    robot_position: (isize, isize),
-}
-
-impl From<Vec<Vec<Tile>>> for FatGrid {
-    fn from(value: Vec<Vec<Tile>>) -> Self {
-        let elements: Vec<Vec<FatTile>> = value
-            .into_iter()
-            .map(|row| {
-                row.into_iter()
-                    .flat_map(|tile| match tile {
-                        Tile::Box => [FatTile::LeftBox, FatTile::RightBox],
-                        Tile::Empty => [FatTile::Empty; 2],
-                        Tile::Wall => [FatTile::Wall; 2],
-                        Tile::Robot => [FatTile::Robot, FatTile::Empty],
-                    })
-                    .collect()
+    elements: Cells<FatTile>,
+    robot_position: Point,
+}
+
+impl From<Cells<Tile>> for FatGrid {
+    fn from(value: Cells<Tile>) -> Self {
+        let cells = value
+            .rows()
+            .flatten()
+            .flat_map(|tile| match tile {
+                Tile::Box => [FatTile::LeftBox, FatTile::RightBox],
+                Tile::Empty => [FatTile::Empty; 2],
+                Tile::Wall => [FatTile::Wall; 2],
+                Tile::Robot => [FatTile::Robot, FatTile::Empty],
             })
             .collect();
-        let robot_position = elements
-            .iter()
-            .enumerate()
-            .find_map(|(row_id, r)| {
-                r.iter()
-                    .find_position(|el| **el == FatTile::Robot)
-                    .map(|(col_id, _)| (row_id as isize, col_id as isize))
-            })
-            .unwrap();
+        let elements = Cells::from_vec(value.width() * 2, value.height(), cells);
+        let robot_position = find_robot(&elements, FatTile::Robot);
         Self {
             elements,
             robot_position,
@@ -265,22 +214,12 @@ impl From<Vec<Vec<Tile>>> for FatGrid {
 }
 
 impl FatGrid {
-    #[allow(dead_code)]
-    fn print_grid(&self) {
-        for row in &self.elements {
-            for col in row {
-                eprint!("{col}");
-            }
-            eprintln!();
-        }
+    fn get_at(&self, pos: Point) -> FatTile {
+        self.elements[pos]
     }
 
-    fn get_at(&self, (row, col): (isize, isize)) -> FatTile {
-        self.elements[row as usize][col as usize]
-    }
-
-    fn update_at(&mut self, (row, col): (isize, isize), value: FatTile) {
-        self.elements[row as usize][col as usize] = value
+    fn update_at(&mut self, pos: Point, value: FatTile) {
+        self.elements[pos] = value
     }
 
     fn get_affected_tiles(&self, dir: Direction) -> CanGo {
@@ -296,7 +235,7 @@ impl FatGrid {
             }
             targets.insert(pos);
 
-            let new_pos = add_coords(pos, dir.get_coord());
+            let new_pos = pos + dir.offset();
             let tile = self.get_at(new_pos);
 
             match tile {
@@ -304,11 +243,11 @@ impl FatGrid {
                 FatTile::Empty => (),
                 FatTile::Wall => return CanGo::NoPush,
                 FatTile::RightBox => {
-                    to_visit.insert(add_coords(new_pos, Direction::Left.get_coord()));
+                    to_visit.insert(new_pos + Direction::West.offset());
                     to_visit.insert(new_pos);
                 }
                 FatTile::LeftBox => {
-                    to_visit.insert(add_coords(new_pos, Direction::Right.get_coord()));
+                    to_visit.insert(new_pos + Direction::East.offset());
                     to_visit.insert(new_pos);
                 }
             }
@@ -331,7 +270,7 @@ impl FatGrid {
                     .for_each(|(pos, _)| self.update_at(*pos, FatTile::Empty));
 
                 btree_set.iter().for_each(|(pos, tile)| {
-                    let new_pos = add_coords(*pos, dir.get_coord());
+                    let new_pos = *pos + dir.offset();
                     if *tile == FatTile::Robot {
                         self.robot_position = new_pos;
                     }
@@ -345,32 +284,24 @@ impl FatGrid {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum CanGo {
-    Push(BTreeSet<((isize, isize), FatTile)>),
+    Push(BTreeSet<(Point, FatTile)>),
     NoPush,
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: Input) -> Option<u64> {
     let (grid, dirs) = parse(input);
     let mut grid = FatGrid::from(grid.elements);
-
-    // grid.print_grid();
+    recorder::push_frame(&grid.elements, colour);
 
     for dir in dirs {
         grid.move_to(dir);
+        recorder::push_frame(&grid.elements, colour);
     }
 
-    // grid.print_grid();
-
     Some(
         grid.elements
             .iter()
-            .enumerate()
-            .flat_map(|(row_idx, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(move |(col_idx, tile)| (row_idx, col_idx, tile))
-            })
-            .filter_map(|(row_idx, col_idx, tile)| match tile {
+            .filter_map(|((row_idx, col_idx), tile)| match tile {
                 FatTile::LeftBox => Some(100 * row_idx as u64 + col_idx as u64),
                 _ => None,
             })
@@ -378,19 +309,279 @@ pub fn part_two(input: &str) -> Option<u64> {
     )
 }
 
+/* ------------------------------- playback ------------------------------- */
+
+/// A warehouse that can be stepped through with `--play`.
+trait Warehouse: Clone {
+    type Tile: Display;
+
+    fn cells(&self) -> &Cells<Self::Tile>;
+
+    /// Moves the robot, returning the positions the pushed boxes ended up at.
+    fn push(&mut self, dir: Direction) -> Vec<Point>;
+}
+
+impl Warehouse for Grid {
+    type Tile = Tile;
+
+    fn cells(&self) -> &Cells<Tile> {
+        &self.elements
+    }
+
+    fn push(&mut self, dir: Direction) -> Vec<Point> {
+        let mut boxes = vec![];
+        let mut pos = self.robot_location + dir.offset();
+        while self.get_at(pos) == Tile::Box {
+            boxes.push(pos + dir.offset());
+            pos = pos + dir.offset();
+        }
+        if self.get_at(pos) == Tile::Wall {
+            boxes.clear();
+        }
+
+        move_robot_in_dir(self, dir);
+        boxes
+    }
+}
+
+impl Warehouse for FatGrid {
+    type Tile = FatTile;
+
+    fn cells(&self) -> &Cells<FatTile> {
+        &self.elements
+    }
+
+    fn push(&mut self, dir: Direction) -> Vec<Point> {
+        let boxes = match self.get_affected_tiles(dir) {
+            CanGo::Push(tiles) => tiles
+                .into_iter()
+                .filter(|(_, tile)| *tile != FatTile::Robot)
+                .map(|(pos, _)| pos + dir.offset())
+                .collect(),
+            CanGo::NoPush => vec![],
+        };
+
+        self.move_to(dir);
+        boxes
+    }
+}
+
+/// Steps through the warehouse of both parts if the solution was called with `--play`,
+/// e.g. `cargo solve 15 -- --play`.
+fn play_from_args(input: &str) {
+    if !solution_flag("--play") {
+        return;
+    }
+    if !io::stdin().is_terminal() {
+        eprintln!("`--play` needs an interactive terminal.");
+        return;
+    }
+
+    let (grid, dirs) = parse(Input::new(input));
+    play(grid.clone(), &dirs);
+    play(FatGrid::from(grid.elements), &dirs);
+}
+
+/// A state of the warehouse during playback.
+struct Step<W> {
+    warehouse: W,
+    /// The move that led to this state, and whether it was taken from the move list.
+    dir: Option<(Direction, bool)>,
+    pushed: Vec<Point>,
+}
+
+enum Key {
+    Next,
+    Back,
+    Move(Direction),
+    Quit,
+    Other,
+}
+
+/// Steps through the move list interactively, each state is kept so moves can be undone.
+fn play<W: Warehouse>(warehouse: W, dirs: &[Direction]) {
+    let _terminal = match RawTerminal::enable() {
+        Ok(terminal) => terminal,
+        Err(e) => {
+            eprintln!("Failed to set up the terminal: {e}");
+            return;
+        }
+    };
+
+    let mut history = vec![Step {
+        warehouse,
+        dir: None,
+        pushed: vec![],
+    }];
+    let mut next_move = 0;
+    let mut stdin = io::stdin().lock();
+
+    loop {
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(draw(&history, next_move, dirs.len()).as_bytes());
+        let _ = stdout.flush();
+
+        let (dir, scripted) = match read_key(&mut stdin) {
+            Key::Next if next_move < dirs.len() => (dirs[next_move], true),
+            Key::Move(dir) => (dir, false),
+            Key::Back => {
+                if history.len() > 1 {
+                    if let Some(Step {
+                        dir: Some((_, true)),
+                        ..
+                    }) = history.pop()
+                    {
+                        next_move -= 1;
+                    }
+                }
+                continue;
+            }
+            Key::Quit => break,
+            Key::Next | Key::Other => continue,
+        };
+
+        let mut warehouse = history.last().unwrap().warehouse.clone();
+        let pushed = warehouse.push(dir);
+        history.push(Step {
+            warehouse,
+            dir: Some((dir, scripted)),
+            pushed,
+        });
+        if scripted {
+            next_move += 1;
+        }
+    }
+}
+
+fn read_key(stdin: &mut impl Read) -> Key {
+    let mut byte = || {
+        let mut buf = [0];
+        stdin.read_exact(&mut buf).ok().map(|()| buf[0])
+    };
+
+    match byte() {
+        None | Some(b'q') => Key::Quit,
+        Some(b'n' | b' ') => Key::Next,
+        Some(b'b' | 0x7f) => Key::Back,
+        // arrow keys are sent as `ESC [ A` to `ESC [ D`.
+        Some(0x1b) => match (byte(), byte()) {
+            (Some(b'['), Some(b'A')) => Key::Move(Direction::North),
+            (Some(b'['), Some(b'B')) => Key::Move(Direction::South),
+            (Some(b'['), Some(b'C')) => Key::Move(Direction::East),
+            (Some(b'['), Some(b'D')) => Key::Move(Direction::West),
+            _ => Key::Other,
+        },
+        Some(_) => Key::Other,
+    }
+}
+
+/// Draws the current state, with the boxes pushed by the last move highlighted.
+fn draw<W: Warehouse>(history: &[Step<W>], next_move: usize, total_moves: usize) -> String {
+    let step = history.last().unwrap();
+    let mut out = String::from("\x1b[H\x1b[2J");
+
+    for (row_idx, row) in step.warehouse.cells().rows().enumerate() {
+        for (col_idx, tile) in row.iter().enumerate() {
+            let tile = tile.to_string();
+            let [r, g, b] = colour(tile.chars().next().unwrap_or(' '));
+            let highlight = if step.pushed.contains(&Point::new(row_idx, col_idx)) {
+                "\x1b[7m"
+            } else {
+                ""
+            };
+            let _ = write!(out, "{highlight}\x1b[38;2;{r};{g};{b}m{tile}\x1b[0m");
+        }
+        out.push_str("\r\n");
+    }
+
+    let last_move = match step.dir {
+        Some((dir, true)) => format!("moved {}", dir.arrow()),
+        Some((dir, false)) => format!("moved {} by hand", dir.arrow()),
+        None => "start".to_string(),
+    };
+    let _ = write!(
+        out,
+        "\r\nmove {next_move}/{total_moves}, {last_move}, pushed {} tile(s)\r\n\
+         n/space: next move  b/backspace: undo  arrows: move robot  q: quit\r\n",
+        step.pushed.len()
+    );
+    out
+}
+
+/// Puts the terminal into non-canonical mode without echo for the lifetime of the value,
+/// so single key presses can be read. Uses `stty` to avoid pulling in a terminal library.
+struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    fn enable() -> io::Result<Self> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "min", "1"])?;
+        // switch to the alternate screen and hide the cursor.
+        print!("\x1b[?1049h\x1b[?25l");
+        Ok(Self {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(Input::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(Input::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(9021));
     }
+
+    #[test]
+    fn test_pushed_boxes() {
+        let (mut grid, _) = parse(Input::new("#######\n#.@OO.#\n#######\n\n>"));
+        assert_eq!(
+            grid.push(Direction::East),
+            vec![Point::new(1, 4), Point::new(1, 5)]
+        );
+        assert!(grid.push(Direction::East).is_empty());
+
+        let (grid, _) = parse(Input::new(
+            "#######\n#.....#\n#..O..#\n#..@..#\n#######\n\n^",
+        ));
+        let mut grid = FatGrid::from(grid.elements);
+        let pushed = grid.push(Direction::North);
+        assert_eq!(pushed, vec![Point::new(1, 6), Point::new(1, 7)]);
+        assert!(grid.push(Direction::North).is_empty());
+    }
 }
@@ -1,394 +1,327 @@
-use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::{fmt::Write as _, fs, path::PathBuf};
 
-use itertools::Itertools;
+use advent_of_code::{
+    grid::Grid,
+    image::Rgb,
+    point::{Direction, Point},
+    search::{astar_with_index, DenseIndex, SearchResult, StateIndex},
+    template::solution_arg,
+};
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, extras = visualize_from_args);
 
-fn parse(input: &str) -> HashMap<(usize, usize), char> {
-    input
-        .trim()
-        .split('\n')
-        .enumerate()
-        .flat_map(|(row_idx, row)| {
-            row.chars()
-                .enumerate()
-                .map(move |(col_idx, ch)| ((row_idx, col_idx), ch))
-        })
-        .collect()
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
-enum Direction {
-    North,
-    West,
-    South,
-    East,
-}
-
-impl Direction {
-    const fn opposite(&self) -> Direction {
-        match self {
-            Direction::North => Direction::South,
-            Direction::West => Direction::East,
-            Direction::South => Direction::North,
-            Direction::East => Direction::West,
-        }
+fn turn_cost(from: Direction, to: Direction) -> i64 {
+    if from == to {
+        return 0;
     }
-
-    fn turn_cost(&self, other: &Direction) -> i64 {
-        if self == other {
-            return 0;
-        }
-        if self.opposite() == *other {
-            return 2000;
-        }
-        1000
+    if from.opposite() == to {
+        return 2000;
     }
+    1000
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
 struct Node {
-    position: (usize, usize),
+    position: Point,
     direction: Direction,
 }
 
-impl Node {
-    fn moved_to_dir(&self) -> Self {
-        let (r, c) = self.position;
-        let position = match self.direction {
-            Direction::North => (r - 1, c),
-            Direction::West => (r, c - 1),
-            Direction::South => (r + 1, c),
-            Direction::East => (r, c + 1),
-        };
-        Self {
-            position,
-            direction: self.direction,
-        }
-    }
+struct Maze {
+    grid: Grid<char>,
+    start: Point,
+    end: Point,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct State {
-    cost: i64,
-    node: Node,
-}
+impl Maze {
+    fn parse(input: &str) -> Self {
+        let grid = Grid::parse(input.trim(), |ch| ch);
+        let start = grid.find(|ch| *ch == 'S').unwrap().into();
+        let end = grid.find(|ch| *ch == 'E').unwrap().into();
+        Self { grid, start, end }
+    }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost)
+    fn node_count(&self) -> usize {
+        self.grid.width() * self.grid.height() * 4
     }
-}
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    /// The dense index of a node, `(row * width + col) * 4 + direction`.
+    fn index(&self, node: Node) -> usize {
+        (node.position.row * self.grid.width() + node.position.col) * 4 + node.direction as usize
     }
-}
 
-pub fn part_one(input: &str) -> Option<i64> {
-    #[cfg(debug_assertions)]
-    let height = input.trim().split("\n").count();
-    #[cfg(debug_assertions)]
-    let width = input.trim().split("\n").next().unwrap().len();
-    let input = parse(input);
-    let start = input.iter().find(|(_, ch)| **ch == 'S').unwrap();
-    #[cfg(debug_assertions)]
-    let end = input.iter().find(|(_, ch)| **ch == 'E').unwrap();
-
-    let mut queue = BinaryHeap::new();
-    let mut distances = HashMap::new();
-    let mut prev = HashMap::new();
-
-    queue.push(State {
-        cost: 0,
-        node: Node {
-            position: *start.0,
-            direction: Direction::East,
-        },
-    });
-
-    for (k, _) in input.iter() {
-        for dir in [
-            Direction::North,
-            Direction::West,
-            Direction::South,
-            Direction::East,
-        ] {
-            distances.insert(
-                Node {
-                    position: *k,
-                    direction: dir,
-                },
-                i64::MAX,
-            );
-            // queue.push_back(k);
+    fn node(&self, index: usize) -> Node {
+        let cell = index / 4;
+        Node {
+            position: Point::new(cell / self.grid.width(), cell % self.grid.width()),
+            direction: Direction::ALL[index % 4],
         }
     }
 
-    distances
-        .entry(Node {
-            position: *start.0,
-            direction: Direction::East,
-        })
-        .insert_entry(0);
+    fn is_open(&self, position: Point) -> bool {
+        self.grid[position] != '#'
+    }
 
-    let mut tc = None;
+    /// Turning in place, or moving forward if there is no wall in the way.
+    fn neighbours(&self, node: Node) -> impl Iterator<Item = (Node, i64)> + '_ {
+        let forward = self
+            .grid
+            .step(node.position, node.direction)
+            .filter(|position| self.is_open(*position))
+            .map(|position| (Node { position, ..node }, 1));
 
-    'outer: while let Some(coords) = queue.pop() {
-        let base_cost = match input[&coords.node.position] {
-            '#' => continue,
-            'E' => {
-                tc = Some(coords.cost);
-                break 'outer;
-            }
-            _ => distances[&coords.node],
-        };
+        self.turns(node).chain(forward)
+    }
 
-        for dir in [
-            Direction::West,
-            Direction::South,
-            Direction::East,
-            Direction::North,
-        ] {
-            if dir == coords.node.direction {
-                continue;
-            }
-            let state_turned_node = Node {
-                direction: dir,
-                position: coords.node.position,
-            };
-            let cost = base_cost + dir.turn_cost(&coords.node.direction);
-            if cost < distances[&state_turned_node] {
-                distances.entry(state_turned_node).insert_entry(cost);
-                prev.entry(state_turned_node).insert_entry(coords.node);
-                queue.push(State {
-                    cost,
-                    node: state_turned_node,
-                });
-            }
-        }
-        let next_pos = coords.node.moved_to_dir();
-        let cost = base_cost + 1;
-
-        if cost < distances[&next_pos] {
-            distances.entry(next_pos).insert_entry(cost);
-            prev.entry(next_pos).insert_entry(coords.node);
-            queue.push(State {
-                cost,
-                node: next_pos,
-            });
-        }
+    fn turns(&self, node: Node) -> impl Iterator<Item = (Node, i64)> {
+        Direction::ALL
+            .into_iter()
+            .filter(move |dir| *dir != node.direction)
+            .map(move |direction| {
+                let turned = Node { direction, ..node };
+                (turned, turn_cost(node.direction, direction))
+            })
     }
 
-    // tc = distances
-    //     .iter()
-    //     .filter(|el| el.0.position == *end.0)
-    //     .map(|el| el.1)
-    //     .min()
-    //     .cloned();
+    /// A lower bound of the cost to the end: the Manhattan distance and the turns that cannot be avoided.
+    /// It is consistent, so A* still finds the predecessors of all nodes on cheapest paths.
+    fn heuristic(&self, node: Node) -> i64 {
+        let Node {
+            position,
+            direction,
+        } = node;
+
+        let (dy, dx) = (
+            self.end.row.cmp(&position.row),
+            self.end.col.cmp(&position.col),
+        );
+        let (behind, sideways) = match direction {
+            Direction::North => (dy.is_gt(), dx.is_ne()),
+            Direction::South => (dy.is_lt(), dx.is_ne()),
+            Direction::East => (dx.is_lt(), dy.is_ne()),
+            Direction::West => (dx.is_gt(), dy.is_ne()),
+        };
+
+        // turning around, or turning to one side and then again to the other.
+        let turns = if behind {
+            2
+        } else if sideways {
+            1
+        } else {
+            0
+        };
 
-    if tc.is_none() {
-        panic!("No route found")
+        (position.manhattan_distance(self.end) + 1000 * turns) as i64
     }
+}
 
-    #[cfg(debug_assertions)]
-    {
-        let mut seq = VecDeque::new();
-
-        let mut u = prev
-            .keys()
-            .find(|Node { position, .. }| *position == *end.0)
-            .unwrap()
-            .to_owned();
+/// A* over the densely indexed nodes, which continues until every cheapest path to the end is known.
+fn find_paths(maze: &Maze) -> SearchResult<Node, i64, impl StateIndex<Node> + '_> {
+    let start = Node {
+        position: maze.start,
+        direction: Direction::East,
+    };
+    let index = DenseIndex::new(
+        maze.node_count(),
+        |node: &Node| maze.index(*node),
+        |index| maze.node(index),
+    );
+
+    astar_with_index(
+        start,
+        index,
+        |node| maze.neighbours(*node),
+        |node| maze.heuristic(*node),
+        |node| node.position == maze.end,
+    )
+}
 
-        while u.position != *start.0 {
-            seq.push_front(u);
-            u = prev[&u]
-        }
+/// The cost of the cheapest path and the number of tiles on any cheapest path. The tiles are
+/// collected by a backward sweep over the predecessors of the search.
+fn solve(input: &str) -> Option<(i64, i64)> {
+    let maze = Maze::parse(input);
+    let paths = find_paths(&maze);
 
-        // dbg!(seq);
-        for r in 0..height {
-            for c in 0..width {
-                let ch = match seq.iter().find(|el| el.position == (r, c)) {
-                    Some(Node { direction, .. }) => match direction {
-                        Direction::North => '^',
-                        Direction::West => '<',
-                        Direction::South => 'v',
-                        Direction::East => '>',
-                    },
-                    None => input[&(r, c)],
-                };
-                eprint!("{}", ch);
-            }
-            eprintln!()
-        }
+    let mut tiles = maze.grid.map(|_| false);
+    for node in paths.optimal_states() {
+        tiles[node.position] = true;
     }
 
-    tc
+    let cost = paths.cost()?;
+    Some((cost, tiles.iter().filter(|(_, on)| **on).count() as i64))
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    solve(input).map(|(cost, _)| cost)
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    #[cfg(debug_assertions)]
-    let height = input.trim().split("\n").count();
-    #[cfg(debug_assertions)]
-    let width = input.trim().split("\n").next().unwrap().len();
-    let input = parse(input);
-    let start = input.iter().find(|(_, ch)| **ch == 'S').unwrap();
-    let end = input.iter().find(|(_, ch)| **ch == 'E').unwrap();
-
-    let mut queue = BinaryHeap::new();
-    let mut distances = HashMap::new();
-    let mut prev = HashMap::new();
-
-    queue.push(State {
-        cost: 0,
-        node: Node {
-            position: *start.0,
-            direction: Direction::East,
-        },
-    });
-
-    for (k, _) in input.iter() {
-        for dir in [
-            Direction::North,
-            Direction::West,
-            Direction::South,
-            Direction::East,
-        ] {
-            distances.insert(
-                Node {
-                    position: *k,
-                    direction: dir,
-                },
-                i64::MAX,
-            );
-        }
-    }
+    solve(input).map(|(_, tiles)| tiles)
+}
 
-    distances
-        .entry(Node {
-            position: *start.0,
-            direction: Direction::East,
-        })
-        .insert_entry(0);
-
-    'outer: while let Some(coords) = queue.pop() {
-        let base_cost = match input[&coords.node.position] {
-            '#' => continue,
-
-            'E' => {
-                match coords.cost.cmp(&distances[&coords.node]) {
-                    std::cmp::Ordering::Less => {}
-                    std::cmp::Ordering::Equal => (),
-                    std::cmp::Ordering::Greater => {
-                        break 'outer;
-                    }
-                }
-                continue;
-            }
+/* ----------------------------- visualization ----------------------------- */
 
-            _ => distances[&coords.node],
-        };
-        for dir in [
-            Direction::North,
-            Direction::West,
-            Direction::South,
-            Direction::East,
-        ] {
-            if dir == coords.node.direction {
-                continue;
-            }
-            let self_turned = Node {
-                position: coords.node.position,
-                direction: dir,
-            };
-            let cost = base_cost + dir.turn_cost(&coords.node.direction);
-
-            match cost.cmp(&distances[&self_turned]) {
-                std::cmp::Ordering::Less => {
-                    distances.entry(self_turned).insert_entry(cost);
-
-                    prev.entry(self_turned)
-                        .insert_entry(HashSet::from([coords.node]));
-                    queue.push(State {
-                        cost,
-                        node: self_turned,
-                    });
-                }
-                std::cmp::Ordering::Equal => {
-                    prev.entry(self_turned).and_modify(|v| {
-                        v.insert(coords.node);
-                    });
-                }
-                std::cmp::Ordering::Greater => {
-                    continue;
-                }
-            }
-        }
+/// Upper bound of the number of optimal paths that are drawn.
+const MAX_PATHS: usize = 12;
 
-        let next_pos = coords.node.moved_to_dir();
+const PATH_COLOURS: [Rgb; 6] = [
+    [230, 80, 80],
+    [80, 200, 120],
+    [90, 140, 240],
+    [230, 190, 60],
+    [200, 100, 220],
+    [70, 200, 210],
+];
 
-        let cost = base_cost + 1;
+enum Visualization {
+    Ansi,
+    Svg(PathBuf),
+}
 
-        match cost.cmp(&distances[&next_pos]) {
-            std::cmp::Ordering::Less => {
-                distances.entry(next_pos).insert_entry(cost);
-                prev.entry(next_pos)
-                    .insert_entry(HashSet::from([coords.node]));
-                queue.push(State {
-                    cost,
-                    node: next_pos,
-                });
-            }
-            std::cmp::Ordering::Equal => {
-                prev.entry(next_pos).and_modify(|v| {
-                    v.insert(coords.node);
-                });
-            }
-            std::cmp::Ordering::Greater => {
-                continue;
-            }
-        }
-    }
+/// `--visualize` draws the optimal paths to the terminal, `--visualize <file>` writes them to an SVG file,
+/// e.g. `cargo solve 16 -- --visualize paths.svg`.
+fn visualize_from_args(input: &str) {
+    let target = match solution_arg("--visualize") {
+        Some(Some(path)) => Visualization::Svg(PathBuf::from(path)),
+        Some(None) => Visualization::Ansi,
+        None => return,
+    };
+
+    let maze = Maze::parse(input);
+    visualize(&maze, &find_paths(&maze), &target);
+}
 
-    let mut tiles = BTreeSet::new();
+/// The number of steps forward and of 90 degree turns along a path.
+fn path_stats(path: &[Node]) -> (usize, usize) {
+    path.windows(2)
+        .fold((0, 0), |(steps, turns), pair| match pair {
+            [a, b] if a.position != b.position => (steps + 1, turns),
+            [a, b] if a.direction.opposite() == b.direction => (steps, turns + 2),
+            _ => (steps, turns + 1),
+        })
+}
 
-    let s = *prev
-        .keys()
-        .filter(|el| el.position == *end.0)
-        .min_by(|el1, el2| distances[el1].cmp(&distances[el2]))
-        .unwrap();
+fn path_colour(idx: usize) -> Rgb {
+    PATH_COLOURS[idx % PATH_COLOURS.len()]
+}
 
-    let mut q = VecDeque::from([s]);
+fn visualize(
+    maze: &Maze,
+    result: &SearchResult<Node, i64, impl StateIndex<Node>>,
+    target: &Visualization,
+) {
+    let grid = &maze.grid;
+    let mut paths = result.all_paths(MAX_PATHS + 1);
+    let truncated = paths.len() > MAX_PATHS;
+    paths.truncate(MAX_PATHS);
+
+    if paths.is_empty() {
+        eprintln!("There is no path from S to E.");
+        return;
+    }
 
-    while let Some(u) = q.pop_front() {
-        tiles.insert(u);
-        if u.position == *start.0 {
-            continue;
-        }
-        for tile in &prev[&u] {
-            if !tiles.contains(tile) {
-                q.push_front(*tile);
+    match target {
+        Visualization::Ansi => eprint!("{}", draw_ansi(grid, &paths)),
+        Visualization::Svg(file) => match fs::write(file, draw_svg(grid, &paths)) {
+            Ok(()) => eprintln!("Wrote {} path(s) to {}", paths.len(), file.display()),
+            Err(e) => eprintln!("Failed to write {}: {e}", file.display()),
+        },
+    }
+
+    for (idx, path) in paths.iter().enumerate() {
+        let (steps, turns) = path_stats(path);
+        let [r, g, b] = path_colour(idx);
+        eprintln!(
+            "\x1b[38;2;{r};{g};{b}mpath {}\x1b[0m: {steps} steps, {turns} turns",
+            idx + 1
+        );
+    }
+    if truncated {
+        eprintln!("Only the first {MAX_PATHS} optimal paths are shown.");
+    }
+}
+
+/// Draws the maze with the paths as arrows. Tiles shared by several paths are drawn in white.
+fn draw_ansi(grid: &Grid<char>, paths: &[Vec<Node>]) -> String {
+    let mut tiles: Grid<Option<(usize, Direction)>> = grid.map(|_| None);
+    let mut shared: Grid<bool> = grid.map(|_| false);
+
+    for (idx, path) in paths.iter().enumerate() {
+        for node in path {
+            match tiles[node.position] {
+                Some((first, _)) if first != idx => shared[node.position] = true,
+                // the last node on a tile shows the direction the path leaves it in.
+                _ => tiles[node.position] = Some((idx, node.direction)),
             }
         }
     }
 
-    let tiles: Vec<(usize, usize)> = tiles.into_iter().map(|el| el.position).unique().collect();
-
-    #[cfg(debug_assertions)]
-    for r in 0..height {
-        for c in 0..width {
-            let ch = match tiles.contains(&(r, c)) {
-                true => 'O',
-                false => input[&(r, c)],
-            };
-            eprint!("{}", ch);
+    let mut out = String::new();
+    for ((row, col), ch) in grid.iter() {
+        if row > 0 && col == 0 {
+            out.push('\n');
         }
-        eprintln!()
+        let pos = Point::new(row, col);
+        let (ch, [r, g, b]) = match tiles[pos] {
+            Some((_, dir)) if *ch == '.' && shared[pos] => (dir.arrow(), [255, 255, 255]),
+            Some((idx, dir)) if *ch == '.' => (dir.arrow(), path_colour(idx)),
+            Some(_) => (*ch, [255, 255, 255]),
+            None if *ch == '#' => ('#', [90, 90, 100]),
+            None => (*ch, [60, 60, 70]),
+        };
+        let _ = write!(out, "\x1b[38;2;{r};{g};{b}m{ch}");
+    }
+    out.push_str("\x1b[0m\n");
+    out
+}
+
+fn draw_svg(grid: &Grid<char>, paths: &[Vec<Node>]) -> String {
+    const CELL: usize = 10;
+    let centre = |pos: Point| (pos.col * CELL + CELL / 2, pos.row * CELL + CELL / 2);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#0f0f23\"/>\n",
+        grid.width() * CELL,
+        grid.height() * CELL
+    );
+
+    for ((row, col), ch) in grid.iter() {
+        let (x, y) = (col * CELL, row * CELL);
+        let _ = match ch {
+            '#' => writeln!(
+                svg,
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"#5a5a64\"/>"
+            ),
+            'S' | 'E' => writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" font-size=\"{CELL}\" text-anchor=\"middle\" fill=\"#ffffff\">{ch}</text>",
+                x + CELL / 2,
+                y + CELL - 1
+            ),
+            _ => Ok(()),
+        };
     }
 
-    Some(tiles.len() as i64)
+    for (idx, path) in paths.iter().enumerate() {
+        let mut points: Vec<Point> = path.iter().map(|node| node.position).collect();
+        points.dedup();
+        let points: Vec<String> = points
+            .into_iter()
+            .map(|pos| {
+                let (x, y) = centre(pos);
+                format!("{x},{y}")
+            })
+            .collect();
+        let [r, g, b] = path_colour(idx);
+        let _ = writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"rgb({r},{g},{b})\" stroke-width=\"3\" stroke-opacity=\"0.7\" stroke-linejoin=\"round\"/>",
+            points.join(" ")
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
 }
 
 #[cfg(test)]
@@ -406,4 +339,58 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(64));
     }
+
+    #[test]
+    fn test_path_stats() {
+        let maze = Maze::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = find_paths(&maze);
+        let paths = result.all_paths(MAX_PATHS);
+        assert!(!paths.is_empty());
+
+        for path in paths {
+            let (steps, turns) = path_stats(&path);
+            assert_eq!(steps + 1000 * turns, 11048);
+        }
+    }
+
+    #[test]
+    fn test_large_maze() {
+        // the generated maze the README benchmarks are measured on.
+        let input = fs::read_to_string("data/examples/16-large.txt").unwrap();
+        assert_eq!(part_one(&input), Some(124424));
+        assert_eq!(part_two(&input), Some(434));
+    }
+
+    #[test]
+    fn test_smaller_example() {
+        let input = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+        assert_eq!(part_one(input), Some(7036));
+        assert_eq!(part_two(input), Some(45));
+    }
+
+    #[test]
+    fn test_heuristic_is_admissible() {
+        let maze = Maze::parse(&advent_of_code::template::read_file("examples", DAY));
+        let paths = find_paths(&maze);
+        let cost = paths.cost().unwrap();
+
+        for node in paths.optimal_states() {
+            assert!(paths.distance(&node).unwrap() + maze.heuristic(node) <= cost);
+        }
+    }
 }
@@ -1,228 +1,102 @@
-use std::str::FromStr;
-
-use itertools::Itertools;
+use advent_of_code::vm::Computer;
 
 advent_of_code::solution!(17);
 
-#[derive(Debug)]
-enum Operator {
-    ADivide = 0,
-    BXorLiteral = 1,
-    Bst = 2,
-    Jnz = 3,
-    Bxc = 4,
-    Out = 5,
-    Bdv = 6,
-    Cdv = 7,
-}
-
-impl From<i64> for Operator {
-    fn from(value: i64) -> Self {
-        match value {
-            0 => Self::ADivide,
-            1 => Self::BXorLiteral,
-            2 => Self::Bst,
-            3 => Self::Jnz,
-            4 => Self::Bxc,
-            5 => Self::Out,
-            6 => Self::Bdv,
-            7 => Self::Cdv,
-            _ => unreachable!(),
-        }
-    }
-}
-
-#[derive(Debug)]
-enum Operand {
-    Value(i64), // 0-3
-    RegA,       // 4
-    RegB,       // 5
-    RegC,       // 6
-}
-
-impl From<i64> for Operand {
-    fn from(value: i64) -> Self {
-        match value {
-            v if v < 0 => unimplemented!(),
-            v if v <= 3 => Self::Value(v),
-            4 => Self::RegA,
-            5 => Self::RegB,
-            6 => Self::RegC,
-            _ => unimplemented!(),
-        }
-    }
-}
-
-#[derive(Debug, Default)]
-struct Computer {
-    reg_a: i64,
-    reg_b: i64,
-    reg_c: i64,
-
-    program: Vec<i64>,
-    program_pointer: usize,
-    out: Vec<i64>,
-}
+/// Programs that have not halted after this many instructions are treated as not halting.
+const MAX_STEPS: usize = 100_000;
 
-impl FromStr for Computer {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.trim().split('\n');
-        let reg_a = s.next().unwrap()[12..].parse()?;
-        let reg_b = s.next().unwrap()[12..].parse()?;
-        let reg_c = s.next().unwrap()[12..].parse()?;
+pub fn part_one(input: &str) -> Option<String> {
+    let mut computer: Computer = input.parse().unwrap();
 
-        let program: Vec<i64> = s.nth(1).unwrap()[9..]
-            .split(',')
-            .map(|el| i64::from_str(el).unwrap())
-            .collect();
+    computer.run_with_limit(MAX_STEPS).ok()?;
 
-        Ok(Computer {
-            reg_a,
-            reg_b,
-            reg_c,
-            program,
-            program_pointer: 0,
-            out: vec![],
-        })
-    }
+    Some(computer.output_string())
 }
 
-impl Computer {
-    fn get_operand(&self, operand: &Operand) -> i64 {
-        match operand {
-            Operand::Value(v) => *v,
-            Operand::RegA => self.reg_a,
-            Operand::RegB => self.reg_b,
-            Operand::RegC => self.reg_c,
-        }
+/// Finds the lowest value of register A that makes the program output itself.
+///
+/// Assumes that every iteration of the program outputs one value and then shifts A right by 3 bits,
+/// i.e. the last output only depends on the highest 3 bits of A. The search therefore builds A
+/// from the highest bits down, keeping every 3-bit extension for which the program outputs
+/// the matching tail of itself. Candidates are tried in ascending order, so the first match is the lowest.
+/// Candidates for which the program fails or does not halt are rejected.
+fn find_quine(computer: &Computer, a: u64, matched: usize) -> Option<u64> {
+    if matched == computer.program.len() {
+        return Some(a);
     }
-    fn perform_operation(&mut self) {
-        let operator = &self.program[self.program_pointer];
-        let operator = Operator::from(*operator);
 
-        let operand = &self.program[self.program_pointer + 1];
+    let tail = &computer.program[computer.program.len() - matched - 1..];
 
-        match operator {
-            Operator::ADivide => {
-                let operand = self.get_operand(&(*operand).into());
-                let res = self.reg_a / 2i64.pow(operand as u32);
-                self.reg_a = res;
-            }
-            Operator::BXorLiteral => {
-                // let operand = self.get_operand(&(*operand).into());
-
-                self.reg_b ^= operand;
-            }
-            Operator::Bst => {
-                let operand = self.get_operand(&(*operand).into());
-                self.reg_b = operand.rem_euclid(8);
-            }
-            Operator::Jnz => {
-                if self.reg_a != 0 {
-                    let operand = self.get_operand(&(*operand).into());
-                    self.program_pointer = operand as usize;
-                    return;
-                }
-            }
-            Operator::Bxc => {
-                self.reg_b ^= self.reg_c;
-            }
-            Operator::Out => {
-                let operand = self.get_operand(&(*operand).into());
-                self.out.push(operand.rem_euclid(8));
-            }
-            Operator::Bdv => {
-                let operand = self.get_operand(&(*operand).into());
-                self.reg_b = self.reg_a / 2i64.pow(operand as u32);
-            }
-            Operator::Cdv => {
-                let operand = self.get_operand(&(*operand).into());
-                self.reg_c = self.reg_a / 2i64.pow(operand as u32);
-            }
-        }
-        self.program_pointer += 2;
-    }
-
-    fn run_to_end(&mut self) {
-        while self.program.len() > self.program_pointer {
-            self.perform_operation();
-        }
-    }
-}
-
-pub fn part_one(input: &str) -> Option<String> {
-    let mut computer: Computer = input.parse().unwrap();
-
-    computer.run_to_end();
-    let res = computer.out.into_iter().join(",");
-
-    Some(res)
+    (0..8)
+        .map(|bits| a << 3 | bits)
+        .filter(|candidate| {
+            *candidate != 0
+                && computer
+                    .output_for(*candidate, MAX_STEPS)
+                    .is_ok_and(|output| output == tail)
+        })
+        .find_map(|candidate| find_quine(computer, candidate, matched + 1))
 }
 
-pub fn part_two(_input: &str) -> Option<i64> {
-    None
+/// Returns `None` if no value of register A makes the program output itself, e.g. for programs
+/// that do not shift A by 3 bits per output.
+pub fn part_two(input: &str) -> Option<u64> {
+    let computer: Computer = input.parse().unwrap();
+    find_quine(&computer, 0, 0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::Part;
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("4635635210".to_owned()));
+        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_owned()));
     }
 
     #[test]
-    fn test_computer_part_one() {
-        let mut computer = Computer {
-            program: vec![2, 6],
-            reg_c: 9,
-            ..Default::default()
-        };
-        computer.run_to_end();
-        assert_eq!(computer.reg_b, 1);
-
-        let mut computer = Computer {
-            reg_a: 10,
-            program: vec![5, 0, 5, 1, 5, 4],
-            ..Default::default()
-        };
-        computer.run_to_end();
-        assert_eq!(computer.out, vec![0, 1, 2]);
-
-        let mut computer = Computer {
-            reg_a: 2024,
-            program: vec![0, 1, 5, 4, 3, 0],
-            ..Default::default()
-        };
-        computer.run_to_end();
-        assert_eq!(computer.out, vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
-        assert_eq!(computer.reg_a, 0);
-
-        let mut computer = Computer {
-            reg_b: 29,
-            program: vec![1, 7],
-            ..Default::default()
-        };
-        computer.run_to_end();
-        assert_eq!(computer.reg_b, 26);
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples",
+            DAY,
+            Part::Two,
+        ));
+        assert_eq!(result, Some(117440));
+    }
 
-        let mut computer = Computer {
-            reg_b: 2024,
-            reg_c: 43690,
-            program: vec![4, 0],
-            ..Default::default()
-        };
-        computer.run_to_end();
-        assert_eq!(computer.reg_b, 44354);
+    #[test]
+    fn test_part_two_verifies_quine() {
+        let computer: Computer =
+            advent_of_code::template::read_file_part("examples", DAY, Part::Two)
+                .parse()
+                .unwrap();
+        assert_eq!(
+            computer.output_for(117440, MAX_STEPS).unwrap(),
+            computer.program
+        );
+        assert_ne!(
+            computer.output_for(117439, MAX_STEPS).unwrap(),
+            computer.program
+        );
     }
 
     #[test]
-    fn test_part_two() {
+    fn test_part_two_without_solution() {
+        // the program of part one shifts A by 1 bit per output, so it can not output itself.
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_part_two_rejects_failing_candidates() {
+        // jumps back to the start forever.
+        let looping = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0";
+        assert_eq!(part_two(looping), None);
+        // combo operand 7 is invalid.
+        let invalid = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 2,7";
+        assert_eq!(part_one(invalid), None);
+        assert_eq!(part_two(invalid), None);
+    }
 }
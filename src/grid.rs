@@ -0,0 +1,305 @@
+//! A dense, row-major two dimensional grid.
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::point::{Direction, Point};
+
+/// A position in a [`Grid`] as `(row, col)`.
+pub type Pos = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A dense grid of cells, stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells does not match `width * height`.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid from lines of characters, mapping every character to a cell.
+    /// Leading and trailing newlines are ignored.
+    ///
+    /// # Panics
+    /// If the lines are not all of the same length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.trim_matches(['\n', '\r']).lines() {
+            let len_before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let line_width = cells.len() - len_before;
+
+            match width {
+                None => width = Some(line_width),
+                Some(w) => assert_eq!(
+                    w, line_width,
+                    "expected every row to have {w} cells, row {height} has {line_width}"
+                ),
+            }
+            height += 1;
+        }
+
+        Self::from_vec(width.unwrap_or_default(), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Converts a position into the index of the cell in the underlying `Vec`.
+    pub fn index_of(&self, (row, col): Pos) -> usize {
+        row * self.width + col
+    }
+
+    /// Converts an index of the underlying `Vec` into a position.
+    pub fn pos_of(&self, index: usize) -> Pos {
+        (index / self.width, index % self.width)
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    /// Returns the cell at `pos`, or `None` if it is out of bounds.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index_of(pos)])
+    }
+
+    /// Returns the cell at `pos` mutably, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index_of(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `pos` by `(d_row, d_col)`, returning `None` if that leaves the grid.
+    pub fn offset(&self, (row, col): Pos, (d_row, d_col): (isize, isize)) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Moves `point` one step in `direction`, returning `None` if that leaves the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point
+            .step(direction)
+            .filter(|next| self.contains((*next).into()))
+    }
+
+    /// Returns the cell at a signed position, wrapping around the edges of the grid.
+    pub fn get_wrapping(&self, (row, col): (isize, isize)) -> &T {
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        let pos = (
+            row.rem_euclid(self.height as isize) as usize,
+            col.rem_euclid(self.width as isize) as usize,
+        );
+        &self[pos]
+    }
+
+    /// The orthogonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_4
+            .iter()
+            .filter_map(move |offset| self.offset(pos, *offset))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |offset| self.offset(pos, *offset))
+    }
+
+    /// All positions of the grid in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// All cells of the grid with their positions, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The rows of the grid as slices.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the position of the first cell that matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.pos_of(index))
+    }
+
+    /// Creates a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid filled with `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::from_vec(width, height, vec![value; width * height])
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.contains(pos), "position {pos:?} is out of bounds");
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.contains(pos), "position {pos:?} is out of bounds");
+        let index = self.index_of(pos);
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        &self[Pos::from(point)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        &mut self[Pos::from(point)]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::point::{Direction, Point};
+
+    fn get_mock_grid() -> Grid<char> {
+        Grid::parse("\nabc\ndef\n", |c| c)
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_ragged_grids() {
+        Grid::parse("abc\nde", |c| c);
+    }
+
+    #[test]
+    fn checked_and_wrapping_indexing() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((1, 1)), Some(&'e'));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(*grid.get_wrapping((-1, -1)), 'f');
+        assert_eq!(*grid.get_wrapping((2, 4)), 'b');
+    }
+
+    #[test]
+    fn steps_inside_the_grid() {
+        let grid = get_mock_grid();
+        let corner = Point::new(1, 2);
+        assert_eq!(grid[corner], 'f');
+        assert_eq!(grid.step(corner, Direction::East), None);
+        assert_eq!(grid.step(corner, Direction::South), None);
+        assert_eq!(grid.step(corner, Direction::North), Some(Point::new(0, 2)));
+        assert_eq!(grid.step(Point::new(0, 0), Direction::West), None);
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = get_mock_grid();
+        let n4: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(n4, vec![(1, 0), (0, 1)]);
+        let n8: Vec<_> = grid.neighbours8((0, 1)).collect();
+        assert_eq!(n8, vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.find(|c| *c == 'e'), Some((1, 1)));
+        assert_eq!(grid.find(|c| *c == 'z'), None);
+        assert_eq!(grid.iter().filter(|(_, c)| **c > 'c').count(), 3);
+    }
+
+    #[test]
+    fn maps_cells() {
+        let grid = get_mock_grid().map(|c| c.to_ascii_uppercase());
+        assert_eq!(grid.to_string(), "ABC\nDEF\n");
+    }
+}
@@ -0,0 +1,61 @@
+//! Writes grids as plain [netpbm](https://netpbm.sourceforge.net/doc/) images, which most image viewers can open.
+use std::fs;
+use std::path::Path;
+
+use crate::grid::Grid;
+
+/// An RGB color.
+pub type Rgb = [u8; 3];
+
+/// Encodes a grid as a plain PBM image, where `true` cells are black.
+pub fn to_pbm(grid: &Grid<bool>) -> String {
+    let mut image = format!("P1\n{} {}\n", grid.width(), grid.height());
+    for row in grid.rows() {
+        let row: Vec<&str> = row.iter().map(|on| if *on { "1" } else { "0" }).collect();
+        image.push_str(&row.join(" "));
+        image.push('\n');
+    }
+    image
+}
+
+/// Encodes a grid of colors as a plain PPM image.
+pub fn to_ppm(grid: &Grid<Rgb>) -> String {
+    let mut image = format!("P3\n{} {}\n255\n", grid.width(), grid.height());
+    for row in grid.rows() {
+        let row: Vec<String> = row.iter().map(|[r, g, b]| format!("{r} {g} {b}")).collect();
+        image.push_str(&row.join("  "));
+        image.push('\n');
+    }
+    image
+}
+
+/// Writes a grid as an image. Files ending in `.ppm` are drawn with `on` and `off` colors,
+/// all other files are written as black and white PBM.
+pub fn write_image(path: &Path, grid: &Grid<bool>, on: Rgb, off: Rgb) -> std::io::Result<()> {
+    let image = if path.extension().is_some_and(|ext| ext == "ppm") {
+        to_ppm(&grid.map(|cell| if *cell { on } else { off }))
+    } else {
+        to_pbm(grid)
+    };
+    fs::write(path, image)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{to_pbm, to_ppm};
+    use crate::grid::Grid;
+
+    #[test]
+    fn encodes_pbm() {
+        let grid = Grid::parse("#.\n.#", |c| c == '#');
+        assert_eq!(to_pbm(&grid), "P1\n2 2\n1 0\n0 1\n");
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let grid = Grid::from_vec(2, 1, vec![[255, 0, 0], [0, 0, 0]]);
+        assert_eq!(to_ppm(&grid), "P3\n2 1\n255\n255 0 0  0 0 0\n");
+    }
+}
@@ -0,0 +1,195 @@
+//! A thin wrapper around puzzle input with the parsing helpers most days need.
+use std::fmt::{Debug, Display};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+/// Puzzle input with trailing newlines removed.
+///
+/// Dereferences to `str`, so all string methods are available as well.
+/// Solutions can take an `Input` instead of a `&str`, the `solution!` macro converts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a>(&'a str);
+
+impl<'a> Input<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self(input.trim_end_matches(['\n', '\r']))
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// The lines of the input, without line endings.
+    pub fn lines(&self) -> impl Iterator<Item = Input<'a>> + 'a {
+        self.0.lines().map(Input)
+    }
+
+    /// Blocks of lines that are separated by one or more blank lines.
+    pub fn blocks(&self) -> impl Iterator<Item = Input<'a>> + 'a {
+        let input = self.0;
+        let mut start = 0;
+        let mut end = 0;
+        let mut blocks = Vec::new();
+
+        for line in input.split_inclusive('\n') {
+            let line_start = end;
+            end += line.len();
+
+            if line.trim_end_matches(['\n', '\r']).is_empty() {
+                if line_start > start {
+                    blocks.push(Input::new(&input[start..line_start]));
+                }
+                start = end;
+            }
+        }
+
+        if end > start {
+            blocks.push(Input::new(&input[start..end]));
+        }
+
+        blocks.into_iter()
+    }
+
+    /// Parses the input as a grid, mapping every character to a cell.
+    ///
+    /// # Panics
+    /// If the lines are not all of the same length.
+    pub fn grid<T>(&self, f: impl FnMut(char) -> T) -> Grid<T> {
+        Grid::parse(self.0, f)
+    }
+
+    /// All signed integers in the input, ignoring any other characters.
+    /// A `-` is treated as a sign when it is directly followed by a digit.
+    pub fn ints(&self) -> impl Iterator<Item = i64> + 'a {
+        let bytes = self.0.as_bytes();
+        let mut pos = 0;
+
+        std::iter::from_fn(move || {
+            while pos < bytes.len() {
+                let negative =
+                    bytes[pos] == b'-' && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit);
+                if negative {
+                    pos += 1;
+                }
+
+                if !bytes[pos].is_ascii_digit() {
+                    pos += 1;
+                    continue;
+                }
+
+                let mut value: i64 = 0;
+                while let Some(digit) = bytes.get(pos).filter(|b| b.is_ascii_digit()) {
+                    value = value * 10 + i64::from(digit - b'0');
+                    pos += 1;
+                }
+
+                return Some(if negative { -value } else { value });
+            }
+            None
+        })
+    }
+
+    /// Parses every line of the input with `FromStr`.
+    ///
+    /// # Panics
+    /// If a line can not be parsed, naming the line that failed.
+    pub fn parse_lines<T>(&self) -> impl Iterator<Item = T> + 'a
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        self.0.lines().enumerate().map(|(i, line)| {
+            line.parse()
+                .unwrap_or_else(|e| panic!("could not parse line {} \"{line}\": {e:?}", i + 1))
+        })
+    }
+}
+
+impl Deref for Input<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(input: &'a str) -> Self {
+        Self::new(input)
+    }
+}
+
+impl<'a> From<Input<'a>> for &'a str {
+    fn from(input: Input<'a>) -> Self {
+        input.0
+    }
+}
+
+impl Display for Input<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Input;
+
+    #[test]
+    fn trims_trailing_newlines() {
+        assert_eq!(Input::new("a\nb\n\n").as_str(), "a\nb");
+        assert_eq!(Input::new("a\r\nb\r\n").as_str(), "a\r\nb");
+        assert_eq!(Input::new("  a  \n").as_str(), "  a  ");
+    }
+
+    #[test]
+    fn splits_lines() {
+        let lines: Vec<_> = Input::new("a\r\nb\nc\n")
+            .lines()
+            .map(|l| l.as_str())
+            .collect();
+        assert_eq!(lines, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let blocks: Vec<_> = Input::new("\na\nb\n\nc\r\n\r\n\r\nd\n")
+            .blocks()
+            .map(|b| b.as_str())
+            .collect();
+        assert_eq!(blocks, vec!["a\nb", "c", "d"]);
+        assert_eq!(Input::new("").blocks().count(), 0);
+    }
+
+    #[test]
+    fn finds_ints() {
+        let ints: Vec<_> = Input::new("p=0,4 v=-3,-100 Button A: X+194, Y=-5-2 -x")
+            .ints()
+            .collect();
+        assert_eq!(ints, vec![0, 4, -3, -100, 194, -5, -2]);
+        assert_eq!(Input::new("no numbers - here").ints().count(), 0);
+    }
+
+    #[test]
+    fn parses_lines() {
+        let nums: Vec<u32> = Input::new("1\n22\n333\n").parse_lines().collect();
+        assert_eq!(nums, vec![1, 22, 333]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2")]
+    fn panics_for_unparsable_lines() {
+        let _: Vec<u32> = Input::new("1\nx\n").parse_lines().collect();
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = Input::new("ab\ncd\n").grid(|c| c);
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[(1, 0)], 'c');
+    }
+}
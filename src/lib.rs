@@ -1,3 +1,17 @@
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
+pub mod answer;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod linear;
+pub mod ocr;
+pub mod point;
+pub mod search;
+pub mod vm;
+
+#[cfg(all(feature = "dhat-heap", feature = "alloc-stats"))]
+compile_error!(
+    "the `dhat-heap` and `alloc-stats` features both install a global allocator and cannot be combined."
+);
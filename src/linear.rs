@@ -0,0 +1,265 @@
+//! Exact solutions of small integer linear systems.
+//!
+//! Everything is computed on `i128`, so there is no rounding involved even for
+//! coefficients in the order of `10^13`.
+
+/// The solutions of a system of two linear equations in two unknowns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solutions {
+    /// The equations contradict each other.
+    None,
+    /// Exactly one, possibly fractional, solution `x[i] / denominator`. The denominator is positive.
+    Unique { x: [i128; 2], denominator: i128 },
+    /// The equations are linearly dependent.
+    /// Every solution of the single equation `a * x + b * y = c` solves the whole system.
+    Line { a: i128, b: i128, c: i128 },
+}
+
+impl Solutions {
+    /// The unique solution, if there is one and it is integral.
+    pub fn integer(&self) -> Option<[i128; 2]> {
+        match *self {
+            Solutions::Unique { x, denominator } => (x[0] % denominator == 0
+                && x[1] % denominator == 0)
+                .then(|| x.map(|x| x / denominator)),
+            _ => None,
+        }
+    }
+}
+
+/// Solves `m * [x, y] = rhs` with Cramer's rule.
+pub fn solve_2x2(m: [[i128; 2]; 2], rhs: [i128; 2]) -> Solutions {
+    let [[a, b], [c, d]] = m;
+    let [e, f] = rhs;
+
+    let det = a * d - b * c;
+    if det != 0 {
+        let x = [e * d - b * f, a * f - e * c];
+        return Solutions::Unique {
+            x: x.map(|x| x * det.signum()),
+            denominator: det.abs(),
+        };
+    }
+
+    // Both rows are multiples of each other. The system is consistent if the augmented
+    // matrix has the same rank, i.e. if all 2x2 minors that include the right-hand side vanish.
+    if a * f - e * c != 0 || b * f - e * d != 0 {
+        return Solutions::None;
+    }
+
+    if [a, b] != [0, 0] {
+        Solutions::Line { a, b, c: e }
+    } else if [c, d] != [0, 0] {
+        Solutions::Line { a: c, b: d, c: f }
+    } else if [e, f] == [0, 0] {
+        Solutions::Line { a: 0, b: 0, c: 0 }
+    } else {
+        Solutions::None
+    }
+}
+
+/// Finds the non-negative integer solution of `m * [x, y] = rhs` that minimises `cost[0] * x + cost[1] * y`.
+/// Costs must not be negative.
+pub fn min_cost_solution(m: [[i128; 2]; 2], rhs: [i128; 2], cost: [i128; 2]) -> Option<[i128; 2]> {
+    match solve_2x2(m, rhs) {
+        Solutions::None => None,
+        solution @ Solutions::Unique { .. } => {
+            solution.integer().filter(|x| x.iter().all(|x| *x >= 0))
+        }
+        Solutions::Line { a, b, c } => min_cost_on_line(a, b, c, cost),
+    }
+}
+
+/// Finds the non-negative integer solution of `a * x + b * y = c` that minimises `cost[0] * x + cost[1] * y`.
+/// Costs must not be negative.
+pub fn min_cost_on_line(a: i128, b: i128, c: i128, cost: [i128; 2]) -> Option<[i128; 2]> {
+    debug_assert!(cost.iter().all(|c| *c >= 0), "costs must not be negative");
+
+    if a == 0 && b == 0 {
+        return (c == 0).then_some([0, 0]);
+    }
+
+    let (g, s, t) = extended_gcd(a, b);
+    if c % g != 0 {
+        return None;
+    }
+
+    // All solutions are `x = x0 + k * db` and `y = y0 - k * da`.
+    let (x0, y0) = (s * (c / g), t * (c / g));
+    let (da, db) = (a / g, b / g);
+
+    // Bounds of `k` for which both `x >= 0` and `y >= 0`, `None` if unbounded.
+    let mut k_min: Option<i128> = None;
+    let mut k_max: Option<i128> = None;
+    let mut restrict = |k: i128, lower: bool| {
+        let bound = if lower { &mut k_min } else { &mut k_max };
+        *bound = Some(match (*bound, lower) {
+            (Some(b), true) => b.max(k),
+            (Some(b), false) => b.min(k),
+            (None, _) => k,
+        });
+    };
+
+    match db.signum() {
+        1 => restrict(div_ceil(-x0, db), true),
+        -1 => restrict(div_floor(-x0, db), false),
+        _ if x0 < 0 => return None,
+        _ => {}
+    }
+    match da.signum() {
+        1 => restrict(div_floor(y0, da), false),
+        -1 => restrict(div_ceil(y0, da), true),
+        _ if y0 < 0 => return None,
+        _ => {}
+    }
+
+    if let (Some(min), Some(max)) = (k_min, k_max) {
+        if min > max {
+            return None;
+        }
+    }
+
+    // The cost is linear in `k`, so the minimum is at one of the bounds.
+    let slope = cost[0] * db - cost[1] * da;
+    let k = if slope > 0 {
+        k_min.or(k_max)
+    } else {
+        k_max.or(k_min)
+    }?;
+
+    Some([x0 + k * db, y0 - k * da])
+}
+
+/// Returns `(g, s, t)` with `g = gcd(a, b) >= 0` and `a * s + b * t = g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Combines congruences `x ≡ r (mod m)` with the Chinese Remainder Theorem.
+///
+/// Returns the smallest non-negative solution and the modulus of all solutions, i.e. the lcm of all moduli.
+/// The moduli do not need to be coprime. Returns `None` if the congruences contradict each other.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        let (g, s, _) = extended_gcd(m1, m2);
+        if (r2 - r1) % g != 0 {
+            return None;
+        }
+        let lcm = m1 / g * m2;
+        let k = ((r2 - r1) / g * s).rem_euclid(m2 / g);
+        Some(((r1 + m1 * k).rem_euclid(lcm), lcm))
+    })
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -div_floor(-a, b)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, min_cost_on_line, min_cost_solution, solve_2x2, Solutions};
+
+    #[test]
+    fn computes_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (7, 0), (0, -7), (17, 5)] {
+            let (g, s, t) = extended_gcd(a, b);
+            assert!(g >= 0);
+            assert_eq!(a * s + b * t, g);
+        }
+        assert_eq!(extended_gcd(240, 46).0, 2);
+    }
+
+    #[test]
+    fn solves_unique_systems() {
+        // Button A: X+94, Y+34; Button B: X+22, Y+67; Prize: X=8400, Y=5400
+        let solution = solve_2x2([[94, 22], [34, 67]], [8400, 5400]);
+        assert_eq!(solution.integer(), Some([80, 40]));
+
+        let solution = solve_2x2([[2, 0], [0, 2]], [1, 2]);
+        assert_eq!(
+            solution,
+            Solutions::Unique {
+                x: [2, 4],
+                denominator: 4
+            }
+        );
+        assert_eq!(solution.integer(), None);
+    }
+
+    #[test]
+    fn handles_large_coefficients() {
+        let prize = 10_000_000_000_000;
+        let solution =
+            min_cost_solution([[26, 67], [66, 21]], [12748 + prize, 12176 + prize], [3, 1]);
+        assert_eq!(solution, Some([118_679_050_709, 103_199_174_542]));
+    }
+
+    #[test]
+    fn handles_singular_systems() {
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 7]), Solutions::None);
+        assert_eq!(
+            solve_2x2([[1, 2], [2, 4]], [3, 6]),
+            Solutions::Line { a: 1, b: 2, c: 3 }
+        );
+        assert_eq!(solve_2x2([[0, 0], [0, 0]], [0, 1]), Solutions::None);
+
+        // Collinear buttons: pressing B (cost 1, moves 4) is cheaper per distance than A (cost 3, moves 2).
+        assert_eq!(
+            min_cost_solution([[2, 4], [2, 4]], [10, 10], [3, 1]),
+            Some([1, 2])
+        );
+        // ...unless A moves far enough to be worth its cost.
+        assert_eq!(
+            min_cost_solution([[8, 2], [8, 2]], [18, 18], [3, 1]),
+            Some([2, 1])
+        );
+    }
+
+    #[test]
+    fn solves_diophantine_lines() {
+        assert_eq!(min_cost_on_line(3, 5, 1, [1, 1]), None);
+        assert_eq!(min_cost_on_line(4, 6, 7, [1, 1]), None);
+        assert_eq!(min_cost_on_line(3, 5, 22, [1, 1]), Some([4, 2]));
+        assert_eq!(min_cost_on_line(3, 5, 22, [1, 100]), Some([4, 2]));
+        assert_eq!(min_cost_on_line(3, 5, 30, [1, 1]), Some([0, 6]));
+        assert_eq!(min_cost_on_line(3, 5, 30, [1, 10]), Some([10, 0]));
+        assert_eq!(min_cost_on_line(0, 5, 30, [1, 1]), Some([0, 6]));
+        assert_eq!(min_cost_on_line(1, -1, 3, [1, 1]), Some([3, 0]));
+    }
+
+    #[test]
+    fn combines_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(12, 101), (50, 103)]).unwrap().0 % 101, 12);
+        assert_eq!(crt(&[(12, 101), (50, 103)]).unwrap().0 % 103, 50);
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
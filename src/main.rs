@@ -1,81 +1,131 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::solve::SubmitOptions;
+use advent_of_code::template::commands::{
+    all, download, leaderboard, read, scaffold, solve, status, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{Day, DaySelection, Part};
+    use std::{collections::HashSet, process};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: HashSet<Day>,
         },
         Read {
-            day: Day,
+            days: HashSet<Day>,
         },
         Scaffold {
-            day: Day,
+            days: HashSet<Day>,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            days: HashSet<Day>,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            alloc_stats: bool,
+            submit: Option<Part>,
+            dry_run: bool,
+            force: bool,
+            record: Option<String>,
+            forward: Vec<String>,
         },
         All {
             release: bool,
         },
+        Status {
+            store: bool,
+        },
+        Leaderboard {
+            id: Option<u64>,
+            file: Option<String>,
+            store: bool,
+        },
         Time {
-            all: bool,
-            day: Option<Day>,
+            days: HashSet<Day>,
             store: bool,
+            fresh_process: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        // everything after `--` is passed on to the solution, e.g. `cargo solve 15 -- --play`.
+        let mut raw_args: Vec<_> = std::env::args_os().skip(1).collect();
+        let mut forward: Vec<String> = match raw_args.iter().position(|arg| arg == "--") {
+            Some(idx) => raw_args
+                .split_off(idx)
+                .into_iter()
+                .skip(1)
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            None => vec![],
+        };
+        let mut args = pico_args::Arguments::from_vec(raw_args);
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
+            Some("status") => AppArguments::Status {
+                store: args.contains("--store"),
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                id: args.opt_value_from_str("--id")?,
+                file: args.opt_value_from_str("--file")?,
+                store: args.contains("--store"),
+            },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let fresh_process = args.contains("--fresh-process");
+
+                // when neither a selector nor the `--all` flag is set, skip days that are fully benched.
+                let selection = args.opt_free_from_str()?.unwrap_or(if all {
+                    DaySelection::All
+                } else {
+                    DaySelection::Unsolved
+                });
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
+                    days: selection.days(),
                     store,
+                    fresh_process,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                days: args.free_from_str::<DaySelection>()?.days(),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                days: args.free_from_str::<DaySelection>()?.days(),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: args.free_from_str::<DaySelection>()?.days(),
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                days: args.free_from_str::<DaySelection>()?.days(),
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
+                dry_run: args.contains("--dry-run"),
+                force: args.contains("--force"),
                 dhat: args.contains("--dhat"),
+                alloc_stats: args.contains("--alloc-stats"),
+                record: args.opt_value_from_str("--record")?,
+                forward: std::mem::take(&mut forward),
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -90,6 +140,11 @@ mod args {
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
+        if !forward.is_empty() {
+            eprintln!(
+                "Warning: only `solve` passes arguments after `--` on, ignoring {forward:?}."
+            );
+        }
 
         Ok(app_args)
     }
@@ -103,42 +158,57 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Status { store } => status::handle(store),
+            AppArguments::Leaderboard { id, file, store } => leaderboard::handle(id, file, store),
+            AppArguments::Time {
+                days,
+                store,
+                fresh_process,
+            } => time::handle(&days, store, fresh_process),
+            AppArguments::Download { days } => download::handle(&days),
+            AppArguments::Read { days } => read::handle(&days),
             AppArguments::Scaffold {
-                day,
+                days,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(&days, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(&days);
                 }
             }
             AppArguments::Solve {
-                day,
+                days,
                 release,
                 dhat,
+                alloc_stats,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                dry_run,
+                force,
+                record,
+                forward,
+            } => {
+                if (dry_run || force) && submit.is_none() {
+                    eprintln!(
+                        "Warning: `--dry-run` and `--force` only apply together with `--submit`."
+                    );
+                }
+                solve::handle(
+                    &days,
+                    release,
+                    dhat,
+                    alloc_stats,
+                    submit.map(|part| SubmitOptions {
+                        part,
+                        dry_run,
+                        force,
+                    }),
+                    record.as_deref(),
+                    &forward,
+                );
             }
+            #[cfg(feature = "today")]
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
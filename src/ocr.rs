@@ -0,0 +1,204 @@
+//! Reads the letters that some puzzles draw with `#` and `.`, in the 4x6 and the 6x10 font.
+//!
+//! Letters are split on empty columns, so the spacing between them does not matter.
+use crate::grid::Grid;
+
+/// The letters of the 4x6 font, e.g. from 2016 day 8 or 2022 day 10.
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The letters of the 6x10 font, e.g. from 2018 day 10.
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the letters drawn by the `true` cells of a grid.
+///
+/// Returns `None` if the drawing does not have the height of a known font or contains an unknown letter.
+pub fn recognize(grid: &Grid<bool>) -> Option<String> {
+    let rows: Vec<&[bool]> = grid.rows().collect();
+    let first = rows.iter().position(|row| row.contains(&true))?;
+    let last = rows.iter().rposition(|row| row.contains(&true))?;
+    let rows = &rows[first..=last];
+
+    let font: &[(char, &str)] = match rows.len() {
+        6 => &SMALL_FONT,
+        10 => &LARGE_FONT,
+        _ => return None,
+    };
+
+    let is_empty = |col: usize| rows.iter().all(|row| !row[col]);
+    let mut text = String::new();
+    let mut col = 0;
+
+    while col < grid.width() {
+        if is_empty(col) {
+            col += 1;
+            continue;
+        }
+
+        let start = col;
+        while col < grid.width() && !is_empty(col) {
+            col += 1;
+        }
+
+        let letter: Vec<&[bool]> = rows.iter().map(|row| &row[start..col]).collect();
+        let (ch, _) = font
+            .iter()
+            .find(|(_, glyph)| matches_glyph(&letter, glyph))?;
+        text.push(*ch);
+    }
+
+    Some(text)
+}
+
+/// Reads the letters of a drawing in which `#` or `█` marks lit pixels.
+pub fn recognize_str(drawing: &str) -> Option<String> {
+    let lines: Vec<&str> = drawing.lines().collect();
+    let width = lines.iter().map(|line| line.chars().count()).max()?;
+    let cells = lines
+        .iter()
+        .flat_map(|line| {
+            let lit: Vec<bool> = line.chars().map(|ch| ch == '#' || ch == '█').collect();
+            let padding = width - lit.len();
+            lit.into_iter().chain(std::iter::repeat_n(false, padding))
+        })
+        .collect();
+
+    recognize(&Grid::from_vec(width, lines.len(), cells))
+}
+
+fn matches_glyph(letter: &[&[bool]], glyph: &str) -> bool {
+    letter.len() == glyph.lines().count()
+        && letter.iter().zip(glyph.lines()).all(|(row, line)| {
+            row.len() == line.len()
+                && row
+                    .iter()
+                    .zip(line.chars())
+                    .all(|(on, ch)| *on == (ch == '#'))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{recognize, recognize_str, LARGE_FONT, SMALL_FONT};
+    use crate::grid::Grid;
+
+    #[test]
+    fn reads_every_letter() {
+        for (ch, glyph) in SMALL_FONT.iter().chain(LARGE_FONT.iter()) {
+            assert_eq!(recognize_str(glyph), Some(ch.to_string()));
+        }
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        // 2022 day 10 draws its letters at a pitch of five columns.
+        let drawing = "\
+###..####.#..#.
+#..#.#....#..#.
+#..#.###..####.
+###..#....#..#.
+#....#....#..#.
+#....####.#..#.";
+        assert_eq!(recognize_str(drawing), Some("PEH".to_string()));
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let drawing: Vec<String> = (0..10)
+            .map(|row| {
+                let h = LARGE_FONT[6].1.lines().nth(row).unwrap();
+                let z = LARGE_FONT[14].1.lines().nth(row).unwrap();
+                format!("..{h}..{z}")
+            })
+            .collect();
+        assert_eq!(recognize_str(&drawing.join("\n")), Some("HZ".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_drawings() {
+        assert_eq!(recognize(&Grid::new(3, 3, true)), None);
+        assert_eq!(recognize(&Grid::new(4, 6, false)), None);
+        assert_eq!(recognize_str("#.#.\n".repeat(6).trim()), None);
+    }
+}
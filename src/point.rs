@@ -0,0 +1,280 @@
+//! Positions, offsets and directions on a two dimensional grid.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::grid::Pos;
+
+/// A position on a grid. Rows grow downwards and columns grow to the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A signed offset between two [`Point`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2 {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Point {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Moves the point by `offset`, returning `None` if a coordinate would become negative.
+    pub fn checked_add(self, offset: Vec2) -> Option<Self> {
+        Some(Self {
+            row: self.row.checked_add_signed(offset.row)?,
+            col: self.col.checked_add_signed(offset.col)?,
+        })
+    }
+
+    /// Moves the point one step in `direction`, returning `None` if it would leave the top or left edge.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.checked_add(direction.offset())
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl Vec2 {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+}
+
+/// Moves a point by an offset.
+///
+/// # Panics
+/// If a coordinate would become negative. Use [`Point::checked_add`] when the result might leave the grid.
+impl Add<Vec2> for Point {
+    type Output = Point;
+
+    fn add(self, offset: Vec2) -> Self::Output {
+        self.checked_add(offset)
+            .unwrap_or_else(|| panic!("moving {self:?} by {offset:?} leaves the grid"))
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Vec2 {
+            row: self.row as isize - other.row as isize,
+            col: self.col as isize - other.col as isize,
+        }
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Self) -> Self::Output {
+        Vec2::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Vec2::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, factor: isize) -> Self::Output {
+        Vec2::new(self.row * factor, self.col * factor)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.row, -self.col)
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((row, col): Pos) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Point> for Pos {
+    fn from(point: Point) -> Self {
+        (point.row, point.col)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal directions, ordered clockwise starting north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// The offset of a single step in this direction.
+    pub const fn offset(self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::new(-1, 0),
+            Direction::East => Vec2::new(0, 1),
+            Direction::South => Vec2::new(1, 0),
+            Direction::West => Vec2::new(0, -1),
+        }
+    }
+
+    /// The arrow that points in this direction, e.g. `^` for north.
+    pub const fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DirectionFromCharError(char);
+
+impl Error for DirectionFromCharError {}
+
+impl Display for DirectionFromCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expecting a direction as one of `^>v<` or `NESW`, got `{}`",
+            self.0
+        )
+    }
+}
+
+/// Parses arrows (`^>v<`) and compass points (`NESW`).
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromCharError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' => Ok(Direction::North),
+            '>' | 'E' => Ok(Direction::East),
+            'v' | 'S' => Ok(Direction::South),
+            '<' | 'W' => Ok(Direction::West),
+            _ => Err(DirectionFromCharError(value)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Point, Vec2};
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point::new(2, 3);
+        assert_eq!(a + Vec2::new(-2, 1), Point::new(0, 4));
+        assert_eq!(a.checked_add(Vec2::new(0, -4)), None);
+        assert_eq!(Point::new(1, 1) - a, Vec2::new(-1, -2));
+        assert_eq!(Vec2::new(1, -2) * 3, Vec2::new(3, -6));
+        assert_eq!(-Vec2::new(1, -2) + Vec2::new(1, 1), Vec2::new(0, 3));
+        assert_eq!(a.manhattan_distance(Point::new(0, 5)), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_when_leaving_the_grid() {
+        let _ = Point::new(0, 0) + Direction::North.offset();
+    }
+
+    #[test]
+    fn steps_in_directions() {
+        let origin = Point::new(0, 0);
+        assert_eq!(origin.step(Direction::North), None);
+        assert_eq!(origin.step(Direction::West), None);
+        assert_eq!(origin.step(Direction::East), Some(Point::new(0, 1)));
+        assert_eq!(origin.step(Direction::South), Some(Point::new(1, 0)));
+    }
+
+    #[test]
+    fn turns_directions() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(dir.offset(), -dir.opposite().offset());
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+    }
+
+    #[test]
+    fn parses_directions() {
+        let arrows: Result<Vec<_>, _> = "^>v<".chars().map(Direction::try_from).collect();
+        let compass: Result<Vec<_>, _> = "NESW".chars().map(Direction::try_from).collect();
+        assert_eq!(arrows.unwrap(), Direction::ALL);
+        assert_eq!(compass.unwrap(), Direction::ALL);
+        assert!(Direction::try_from('x').is_err());
+        assert_eq!(
+            Direction::ALL
+                .map(Direction::arrow)
+                .iter()
+                .collect::<String>(),
+            "^>v<"
+        );
+    }
+}
@@ -0,0 +1,516 @@
+//! Shortest path search over an implicit graph, with Dijkstra or A*.
+//!
+//! States are generated on the fly by a neighbour function, so the graph never has to be built up front.
+//! Every search keeps track of *all* optimal predecessors of a state, which allows to
+//! reconstruct a single shortest path as well as every state that lies on any shortest path.
+//! States are numbered by a [`StateIndex`], by default in the order they are discovered.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Add;
+
+/// A cost of a path. Implemented for all types with a zero value (`Default`), addition and a total order.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// An entry of the priority queue, ordered so that the lowest priority is popped first.
+struct Entry<C> {
+    priority: C,
+    cost: C,
+    state: usize,
+}
+
+impl<C: Ord> PartialEq for Entry<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<C: Ord> Eq for Entry<C> {}
+
+impl<C: Ord> Ord for Entry<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<C: Ord> PartialOrd for Entry<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Numbers the states of a search, so that their distances and predecessors can be kept in vectors.
+pub trait StateIndex<S> {
+    /// The index of `state`, which is assigned if the state was not seen before.
+    fn index(&mut self, state: &S) -> usize;
+
+    /// The index of a state, `None` if it was not seen before.
+    fn get(&self, state: &S) -> Option<usize>;
+
+    /// The state with an index returned by [`StateIndex::index`].
+    fn state(&self, index: usize) -> S;
+
+    /// The number of indices that are known up front, used to size the vectors of a search.
+    fn len_hint(&self) -> usize {
+        0
+    }
+}
+
+/// Numbers states in the order they are discovered. Works for any hashable state.
+#[derive(Debug, Clone)]
+pub struct HashIndex<S> {
+    indices: HashMap<S, usize>,
+    states: Vec<S>,
+}
+
+impl<S> Default for HashIndex<S> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            states: Vec::new(),
+        }
+    }
+}
+
+impl<S: Clone + Eq + Hash> StateIndex<S> for HashIndex<S> {
+    fn index(&mut self, state: &S) -> usize {
+        if let Some(index) = self.indices.get(state) {
+            return *index;
+        }
+        self.states.push(state.clone());
+        self.indices.insert(state.clone(), self.states.len() - 1);
+        self.states.len() - 1
+    }
+
+    fn get(&self, state: &S) -> Option<usize> {
+        self.indices.get(state).copied()
+    }
+
+    fn state(&self, index: usize) -> S {
+        self.states[index].clone()
+    }
+}
+
+/// Indexes states with a function that maps them onto `0..len` and a function that maps them back,
+/// e.g. `(row * width + col) * 4 + direction` for a position and a direction on a grid.
+/// Avoids hashing, which makes searches over large grids a lot faster.
+pub struct DenseIndex<F, G> {
+    len: usize,
+    to_index: F,
+    to_state: G,
+}
+
+impl<F, G> DenseIndex<F, G> {
+    pub fn new(len: usize, to_index: F, to_state: G) -> Self {
+        Self {
+            len,
+            to_index,
+            to_state,
+        }
+    }
+}
+
+impl<S, F: Fn(&S) -> usize, G: Fn(usize) -> S> StateIndex<S> for DenseIndex<F, G> {
+    fn index(&mut self, state: &S) -> usize {
+        (self.to_index)(state)
+    }
+
+    fn get(&self, state: &S) -> Option<usize> {
+        Some((self.to_index)(state)).filter(|index| *index < self.len)
+    }
+
+    fn state(&self, index: usize) -> S {
+        (self.to_state)(index)
+    }
+
+    fn len_hint(&self) -> usize {
+        self.len
+    }
+}
+
+/// The outcome of a search.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C, X = HashIndex<S>> {
+    index: X,
+    start: usize,
+    goals: Vec<usize>,
+    distances: Vec<Option<C>>,
+    predecessors: Predecessors,
+    states: PhantomData<S>,
+}
+
+/// The optimal predecessors of every state, as linked lists in a single vector so that
+/// reaching a state does not allocate.
+#[derive(Debug, Clone, Default)]
+struct Predecessors {
+    /// The first link of every state.
+    heads: Vec<usize>,
+    /// A predecessor and the next link.
+    links: Vec<(usize, usize)>,
+}
+
+impl Predecessors {
+    const END: usize = usize::MAX;
+
+    fn with_len(len: usize) -> Self {
+        Self {
+            heads: vec![Self::END; len],
+            links: vec![],
+        }
+    }
+
+    fn grow(&mut self, len: usize) {
+        if len > self.heads.len() {
+            self.heads.resize(len, Self::END);
+        }
+    }
+
+    /// Replaces the predecessors of `index` with `prev`.
+    fn set(&mut self, index: usize, prev: usize) {
+        self.links.push((prev, Self::END));
+        self.heads[index] = self.links.len() - 1;
+    }
+
+    fn add(&mut self, index: usize, prev: usize) {
+        self.links.push((prev, self.heads[index]));
+        self.heads[index] = self.links.len() - 1;
+    }
+
+    fn of(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut link = self.heads.get(index).copied().unwrap_or(Self::END);
+        std::iter::from_fn(move || {
+            let (prev, next) = *self.links.get(link)?;
+            link = next;
+            Some(prev)
+        })
+    }
+}
+
+impl<S, C: Cost, X: StateIndex<S>> SearchResult<S, C, X> {
+    /// The cost of the cheapest path to a goal, or `None` if no goal is reachable.
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().and_then(|goal| self.distances[*goal])
+    }
+
+    /// All goal states that were reached with the optimal cost.
+    pub fn goals(&self) -> Vec<S> {
+        self.states(&self.goals)
+    }
+
+    /// The cost of the cheapest known path to `state`.
+    ///
+    /// The search stops once all optimal paths to a goal are known, so states that are
+    /// more expensive to reach than a goal might be missing or not be optimal yet.
+    pub fn distance(&self, state: &S) -> Option<C> {
+        let index = self.index.get(state)?;
+        self.distances.get(index).copied().flatten()
+    }
+
+    /// Every state that was reached, with the cost of the cheapest known path to it.
+    pub fn distances(&self) -> impl Iterator<Item = (S, C)> + '_ {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter_map(|(index, distance)| Some((self.index.state(index), (*distance)?)))
+    }
+
+    /// The states that `state` can be reached from on a cheapest path.
+    pub fn predecessors(&self, state: &S) -> Vec<S> {
+        self.index.get(state).map_or_else(Vec::new, |index| {
+            self.predecessors
+                .of(index)
+                .map(|prev| self.index.state(prev))
+                .collect()
+        })
+    }
+
+    /// One cheapest path from the start to a goal, including both.
+    pub fn path(&self) -> Option<Vec<S>> {
+        let mut index = *self.goals.first()?;
+        let mut path = vec![index];
+
+        while index != self.start {
+            index = self.predecessors.of(index).next()?;
+            path.push(index);
+        }
+
+        path.reverse();
+        Some(self.states(&path))
+    }
+
+    /// Every cheapest path from the start to a goal, but at most `limit` of them
+    /// since their number can grow exponentially with the size of the graph.
+    pub fn all_paths(&self, limit: usize) -> Vec<Vec<S>> {
+        let mut paths = vec![];
+        // partial paths, walked backwards from a goal.
+        let mut stack: Vec<Vec<usize>> = self.goals.iter().map(|goal| vec![*goal]).collect();
+
+        while let Some(mut path) = stack.pop() {
+            if paths.len() >= limit {
+                break;
+            }
+
+            let index = *path.last().unwrap();
+            if index == self.start {
+                path.reverse();
+                paths.push(self.states(&path));
+                continue;
+            }
+
+            for prev in self.predecessors.of(index) {
+                let mut path = path.clone();
+                path.push(prev);
+                stack.push(path);
+            }
+        }
+
+        paths
+    }
+
+    /// Every state that lies on at least one cheapest path to a goal.
+    pub fn optimal_states(&self) -> Vec<S> {
+        let mut seen = vec![false; self.distances.len()];
+        let mut stack = self.goals.clone();
+        for goal in &self.goals {
+            seen[*goal] = true;
+        }
+
+        let mut states = vec![];
+        while let Some(index) = stack.pop() {
+            states.push(self.index.state(index));
+            for prev in self.predecessors.of(index) {
+                if !seen[prev] {
+                    seen[prev] = true;
+                    stack.push(prev);
+                }
+            }
+        }
+
+        states
+    }
+
+    fn states(&self, indices: &[usize]) -> Vec<S> {
+        indices
+            .iter()
+            .map(|index| self.index.state(*index))
+            .collect()
+    }
+}
+
+/// Finds the cheapest paths from `start` to any state for which `is_goal` returns `true`.
+///
+/// `neighbours` returns the states reachable from a state together with the non-negative cost of the step.
+/// If no goal is given (e.g. `|_| false`), the whole reachable graph is explored and
+/// [`SearchResult::distances`] contains the shortest distance to every state.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but guided by a `heuristic` that estimates the remaining cost to a goal.
+///
+/// The heuristic must never overestimate the remaining cost. For the predecessors of every
+/// state to be complete, it must also be consistent, i.e. never decrease by more than the cost of a step.
+pub fn astar<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with_index(start, HashIndex::default(), neighbours, heuristic, is_goal)
+}
+
+/// Like [`astar`], but with the states numbered by `index`, e.g. a [`DenseIndex`].
+pub fn astar_with_index<S, C, I, X>(
+    start: S,
+    mut index: X,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C, X>
+where
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    X: StateIndex<S>,
+{
+    let mut queue = BinaryHeap::new();
+    let mut distances: Vec<Option<C>> = vec![None; index.len_hint()];
+    let mut predecessors = Predecessors::with_len(index.len_hint());
+    let mut goals = Vec::new();
+    let mut best = None;
+
+    let start_index = index.index(&start);
+    grow(&mut distances, &mut predecessors, start_index);
+    distances[start_index] = Some(C::default());
+
+    queue.push(Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start_index,
+    });
+
+    while let Some(Entry {
+        priority,
+        cost,
+        state: current,
+    }) = queue.pop()
+    {
+        if best.is_some_and(|best| priority > best) {
+            break;
+        }
+        if distances[current].is_some_and(|known| cost > known) {
+            continue;
+        }
+
+        let state = index.state(current);
+        if is_goal(&state) {
+            best = Some(cost);
+            goals.push(current);
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            let next_index = index.index(&next);
+            grow(&mut distances, &mut predecessors, next_index);
+
+            match distances[next_index].map(|known| next_cost.cmp(&known)) {
+                Some(Ordering::Greater) => {}
+                Some(Ordering::Equal) => predecessors.add(next_index, current),
+                Some(Ordering::Less) | None => {
+                    distances[next_index] = Some(next_cost);
+                    predecessors.set(next_index, current);
+                    queue.push(Entry {
+                        priority: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        state: next_index,
+                    });
+                }
+            }
+        }
+    }
+
+    SearchResult {
+        index,
+        start: start_index,
+        goals,
+        distances,
+        predecessors,
+        states: PhantomData,
+    }
+}
+
+/// Makes room for the state with `index` in the vectors of a search.
+fn grow<C: Copy>(distances: &mut Vec<Option<C>>, predecessors: &mut Predecessors, index: usize) {
+    if index >= distances.len() {
+        distances.resize(index + 1, None);
+        predecessors.grow(index + 1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, astar_with_index, dijkstra, DenseIndex};
+
+    /// A small weighted graph with two cheapest paths from 0 to 4: 0-1-3-4 and 0-2-3-4.
+    fn neighbours(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 2), (4, 10)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 1)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        let result = dijkstra(0, neighbours, |node| *node == 4);
+        assert_eq!(result.cost(), Some(4));
+        assert_eq!(result.goals(), vec![4]);
+        let path = result.path().unwrap();
+        assert!(path == vec![0, 1, 3, 4] || path == vec![0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn tracks_all_optimal_predecessors() {
+        let result = dijkstra(0, neighbours, |node| *node == 4);
+        let mut preds = result.predecessors(&3);
+        preds.sort_unstable();
+        assert_eq!(preds, vec![1, 2]);
+
+        let mut states = result.optimal_states();
+        states.sort_unstable();
+        assert_eq!(states, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn enumerates_all_cheapest_paths() {
+        let result = dijkstra(0, neighbours, |node| *node == 4);
+        let mut paths = result.all_paths(10);
+        paths.sort_unstable();
+        assert_eq!(paths, vec![vec![0, 1, 3, 4], vec![0, 2, 3, 4]]);
+        assert_eq!(result.all_paths(1).len(), 1);
+    }
+
+    #[test]
+    fn explores_everything_without_goal() {
+        let result = dijkstra(0, neighbours, |_| false);
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.path(), None);
+        assert_eq!(result.distance(&3), Some(3));
+        assert_eq!(result.distance(&4), Some(4));
+        assert_eq!(result.distances().count(), 5);
+    }
+
+    #[test]
+    fn returns_none_for_unreachable_goals() {
+        let result = dijkstra(1, neighbours, |node| *node == 0);
+        assert_eq!(result.cost(), None);
+        assert!(result.optimal_states().is_empty());
+    }
+
+    #[test]
+    fn astar_on_a_line() {
+        // walk from 0 to 20 in steps of +1 or +3, with the remaining distance / 3 as heuristic.
+        let result = astar(
+            0_i32,
+            |n| [(n + 1, 1), (n + 3, 1)],
+            |n| ((20 - n).max(0) + 2) / 3,
+            |n| *n == 20,
+        );
+        assert_eq!(result.cost(), Some(8));
+        assert_eq!(result.path().unwrap().len(), 9);
+    }
+
+    #[test]
+    fn astar_with_dense_index() {
+        let index = DenseIndex::new(5, |node: &u8| usize::from(*node), |index| index as u8);
+        let result = astar_with_index(0, index, neighbours, |_| 0, |node| *node == 4);
+        assert_eq!(result.cost(), Some(4));
+        assert_eq!(result.distance(&2), Some(2));
+        assert_eq!(result.distance(&7), None);
+
+        let mut paths = result.all_paths(10);
+        paths.sort_unstable();
+        assert_eq!(paths, vec![vec![0, 1, 3, 4], vec![0, 2, 3, 4]]);
+    }
+}
@@ -0,0 +1,133 @@
+/// A global allocator that counts allocations, as a lightweight alternative to dhat.
+/// Installed by the `solution!` macro when the `alloc-stats` feature is enabled.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static BASELINE_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocation counts since the last call to [`reset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes_allocated: usize,
+    /// The highest number of live bytes, not counting what was live at the time of the reset.
+    pub peak_live_bytes: usize,
+}
+
+/// Start a new measurement.
+pub fn reset() {
+    let live = LIVE_BYTES.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES_ALLOCATED.store(0, Ordering::Relaxed);
+    BASELINE_LIVE_BYTES.store(live, Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live, Ordering::Relaxed);
+}
+
+/// Read the counts of the current measurement.
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed),
+        peak_live_bytes: PEAK_LIVE_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE_LIVE_BYTES.load(Ordering::Relaxed)),
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let bytes_f = bytes as f64;
+    match bytes {
+        b if b < 1024 => format!("{b}B"),
+        b if b < 1024 * 1024 => format!("{:.1}KiB", bytes_f / 1024_f64),
+        _ => format!("{:.1}MiB", bytes_f / (1024_f64 * 1024_f64)),
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes_allocated),
+            format_bytes(self.peak_live_bytes)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+
+    #[test]
+    fn displays_stats() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes_allocated: 2048,
+            peak_live_bytes: 100,
+        };
+        assert_eq!(stats.to_string(), "3 allocs, 2.0KiB allocated, 100B peak");
+    }
+}
@@ -0,0 +1,235 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Part};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the last known answer of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredAnswer {
+    pub day: Day,
+    pub part: Part,
+    /// The last result of the solution, `None` if it did not return a value.
+    pub value: Option<String>,
+    pub submitted: bool,
+    pub accepted: bool,
+}
+
+/// Represents the answers of a set of parts.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<StoredAnswer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: Day, part: Part) -> Option<&StoredAnswer> {
+        self.data.iter().find(|a| a.day == day && a.part == part)
+    }
+
+    fn get_or_insert(&mut self, day: Day, part: Part) -> &mut StoredAnswer {
+        let index = match self
+            .data
+            .iter()
+            .position(|a| a.day == day && a.part == part)
+        {
+            Some(index) => index,
+            None => {
+                self.data.push(StoredAnswer {
+                    day,
+                    part,
+                    value: None,
+                    submitted: false,
+                    accepted: false,
+                });
+                self.data.sort_unstable_by_key(|a| (a.day, a.part));
+                self.data
+                    .iter()
+                    .position(|a| a.day == day && a.part == part)
+                    .unwrap()
+            }
+        };
+        &mut self.data[index]
+    }
+
+    /// Record the latest result of a part.
+    pub fn record_result(&mut self, day: Day, part: Part, value: Option<String>) {
+        self.get_or_insert(day, part).value = value;
+    }
+
+    /// Record that the latest result of a part was submitted.
+    /// A part stays accepted once it has been accepted.
+    pub fn record_submission(&mut self, day: Day, part: Part, accepted: bool) {
+        let answer = self.get_or_insert(day, part);
+        answer.submitted = true;
+        answer.accepted |= accepted;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(StoredAnswer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&StoredAnswer> for JsonValue {
+    fn from(value: &StoredAnswer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::String(value.part.to_string()));
+        map.insert(
+            "value".into(),
+            match value.value.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("submitted".into(), JsonValue::Boolean(value.submitted));
+        map.insert("accepted".into(), JsonValue::Boolean(value.accepted));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for StoredAnswer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<String>())
+            .and_then(|part| Part::from_str(part).ok())
+            .ok_or("Expected answer.part to be a Part.")?;
+
+        let answer_value = json
+            .get("value")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.value to be null or string.")?;
+
+        let submitted = json
+            .get("submitted")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected answer.submitted to be a boolean.")?;
+
+        let accepted = json
+            .get("accepted")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected answer.accepted to be a boolean.")?;
+
+        Ok(StoredAnswer {
+            day,
+            part,
+            value: answer_value.cloned(),
+            submitted,
+            accepted,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::{day, template::Part};
+
+    #[test]
+    fn records_results_and_submissions() {
+        let mut answers = Answers::default();
+        answers.record_result(day!(2), Part::One, Some("42".into()));
+        answers.record_result(day!(1), Part::Two, None);
+        answers.record_submission(day!(2), Part::One, true);
+        answers.record_result(day!(2), Part::One, Some("43".into()));
+        answers.record_submission(day!(2), Part::One, false);
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+
+        let answer = answers.get(day!(2), Part::One).unwrap();
+        assert_eq!(answer.value, Some("43".into()));
+        assert!(answer.submitted);
+        assert!(answer.accepted);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.record_result(day!(1), Part::One, Some("1,2,3".into()));
+        answers.record_result(day!(1), Part::Two, None);
+        answers.record_submission(day!(1), Part::One, false);
+
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.data, answers.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part": "3" }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
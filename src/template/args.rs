@@ -0,0 +1,46 @@
+/// Arguments that are passed on to a solution, e.g. `cargo solve 15 -- --play`.
+use std::env;
+
+/// Whether the solution was called with `name`, e.g. `solution_flag("--play")`.
+#[must_use]
+pub fn solution_flag(name: &str) -> bool {
+    solution_arg(name).is_some()
+}
+
+/// The option `name` and the value that follows it: `None` if the option was not passed, `Some(None)`
+/// if it was passed without a value, e.g. `solution_arg("--image")` for `--image tree.ppm`.
+#[must_use]
+pub fn solution_arg(name: &str) -> Option<Option<String>> {
+    find_arg(env::args().skip(1), name)
+}
+
+fn find_arg(args: impl IntoIterator<Item = String>, name: &str) -> Option<Option<String>> {
+    let mut args = args.into_iter().skip_while(|arg| arg != name);
+    args.next()?;
+    Some(args.next().filter(|value| !value.starts_with("--")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::find_arg;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn finds_flags_and_values() {
+        assert_eq!(find_arg(args("--time --play"), "--play"), Some(None));
+        assert_eq!(
+            find_arg(args("--image tree.ppm"), "--image"),
+            Some(Some("tree.ppm".into()))
+        );
+        assert_eq!(
+            find_arg(args("--visualize --time"), "--visualize"),
+            Some(None)
+        );
+        assert_eq!(find_arg(args("--time"), "--play"), None);
+    }
+}
@@ -1,14 +1,16 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{all_days, aoc_cli, Day};
+use std::{collections::HashSet, process};
 
-pub fn handle(day: Day) {
+pub fn handle(days: &HashSet<Day>) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    for day in all_days().filter(|day| days.contains(day)) {
+        if let Err(e) = aoc_cli::download(day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        };
+    }
 }
@@ -0,0 +1,118 @@
+use std::{process, time::Duration};
+
+use crate::template::leaderboard::{self, Leaderboard, LEADERBOARD_FILE_PATH};
+use crate::template::{
+    aoc_cli, format_duration, readme_leaderboard, Day, Part, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(id: Option<u64>, file: Option<String>, store: bool) {
+    if let Some(id) = id {
+        let Some(year) = aoc_cli::get_year() else {
+            eprintln!("Downloading a leaderboard requires the AOC_YEAR environment variable.");
+            process::exit(1);
+        };
+
+        if let Err(e) = leaderboard::download(year, id) {
+            eprintln!("Failed to download leaderboard: {e}");
+            process::exit(1);
+        }
+    }
+
+    let path = file.unwrap_or_else(|| LEADERBOARD_FILE_PATH.into());
+
+    let leaderboard = match Leaderboard::read_from_file(&path) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Failed to read leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    print_rankings(&leaderboard);
+
+    for day in leaderboard.days_with_stars() {
+        println!();
+        print_day(&leaderboard, day);
+    }
+
+    if store {
+        println!();
+        match readme_leaderboard::update(&leaderboard) {
+            Ok(()) => {
+                println!("Stored updated leaderboard.");
+            }
+            Err(_) => {
+                eprintln!("Failed to store updated leaderboard.");
+            }
+        }
+    }
+}
+
+fn name_width(leaderboard: &Leaderboard) -> usize {
+    leaderboard
+        .members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or_default()
+}
+
+fn print_rankings(leaderboard: &Leaderboard) {
+    let width = name_width(leaderboard);
+
+    println!("{ANSI_BOLD}Leaderboard {}{ANSI_RESET}", leaderboard.year);
+    println!(
+        "{:>4}  {:<width$}  {:>5}  {:>5}",
+        "Rank", "Member", "Stars", "Score"
+    );
+
+    for (rank, member) in leaderboard.rankings().into_iter().enumerate() {
+        println!(
+            "{:>4}  {:<width$}  {:>5}  {:>5}",
+            rank + 1,
+            member.display_name(),
+            member.stars,
+            member.local_score
+        );
+    }
+}
+
+fn print_day(leaderboard: &Leaderboard, day: Day) {
+    let width = name_width(leaderboard);
+    let year = leaderboard.year;
+
+    let mut members: Vec<_> = leaderboard
+        .members
+        .iter()
+        .filter(|m| m.completions.contains_key(&(day, Part::One)))
+        .collect();
+    members.sort_by_key(|m| {
+        (
+            m.solve_time(year, day, Part::Two).unwrap_or(i64::MAX),
+            m.solve_time(year, day, Part::One),
+        )
+    });
+
+    let format_time = |secs: Option<i64>| {
+        secs.and_then(|secs| u64::try_from(secs).ok()).map_or_else(
+            || "-".into(),
+            |secs| format_duration(Duration::from_secs(secs)),
+        )
+    };
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!(
+        "{:<width$}  {:>12}  {:>12}  {:>12}",
+        "Member", "Part 1", "Part 2", "Delta"
+    );
+
+    for member in members {
+        println!(
+            "{:<width$}  {:>12}  {:>12}  {:>12}",
+            member.display_name(),
+            format_time(member.solve_time(year, day, Part::One)),
+            format_time(member.solve_time(year, day, Part::Two)),
+            format_time(member.part_delta(day)),
+        );
+    }
+}
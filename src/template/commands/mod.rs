@@ -1,6 +1,10 @@
 pub mod all;
 pub mod download;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
@@ -1,15 +1,17 @@
-use std::process;
+use std::{collections::HashSet, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{all_days, aoc_cli, Day};
 
-pub fn handle(day: Day) {
+pub fn handle(days: &HashSet<Day>) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    for day in all_days().filter(|day| days.contains(day)) {
+        if let Err(e) = aoc_cli::read(day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        };
+    }
 }
@@ -1,10 +1,11 @@
 use std::{
+    collections::HashSet,
     fs::{File, OpenOptions},
     io::Write,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{all_days, Day};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -27,53 +28,44 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
+pub fn handle(days: &HashSet<Day>, overwrite: bool) {
+    let mut failed = false;
+
+    for day in all_days().filter(|day| days.contains(day)) {
+        if let Err(e) = scaffold_day(day, overwrite) {
+            eprintln!("Skipping day {day}: {e}");
+            failed = true;
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn scaffold_day(day: Day, overwrite: bool) -> Result<(), String> {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let mut file = safe_create_file(&module_path, overwrite)
+        .map_err(|e| format!("Failed to create module file: {e}"))?;
 
-    match file.write_all(
+    file.write_all(
         MODULE_TEMPLATE
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    )
+    .map_err(|e| format!("Failed to write module contents: {e}"))?;
+    println!("Created module file \"{}\"", &module_path);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&input_path).map_err(|e| format!("Failed to create input file: {e}"))?;
+    println!("Created empty input file \"{}\"", &input_path);
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&example_path).map_err(|e| format!("Failed to create example file: {e}"))?;
+    println!("Created empty example file \"{}\"", &example_path);
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
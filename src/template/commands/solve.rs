@@ -1,8 +1,65 @@
-use std::process::{Command, Stdio};
+use std::{
+    collections::HashSet,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::Day;
+use crate::template::{run_multi::run_multi, Day, Part};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// The part to submit with `--submit`, whether to only show what would be sent,
+/// and whether to submit even if the example test of the part fails.
+pub struct SubmitOptions {
+    pub part: Part,
+    pub dry_run: bool,
+    pub force: bool,
+}
+
+/// The outcome of running the example test of a part before submitting it.
+enum ExampleCheck {
+    Passed,
+    /// The day has no `test_part_one` / `test_part_two` test.
+    Missing,
+    /// The test failed, with the details of the failure.
+    Failed(String),
+}
+
+pub fn handle(
+    days: &HashSet<Day>,
+    release: bool,
+    dhat: bool,
+    alloc_stats: bool,
+    submit: Option<SubmitOptions>,
+    record: Option<&str>,
+    forward: &[String],
+) {
+    if dhat && alloc_stats {
+        eprintln!("`--dhat` and `--alloc-stats` cannot be combined.");
+        process::exit(1);
+    }
+
+    if days.len() == 1 {
+        let day = *days.iter().next().unwrap();
+        return solve_day(day, release, dhat, alloc_stats, submit, record, forward);
+    }
+
+    if dhat || alloc_stats || submit.is_some() || record.is_some() || !forward.is_empty() {
+        eprintln!(
+            "`--dhat`, `--alloc-stats`, `--submit`, `--record` and arguments after `--` can only be used when solving a single day."
+        );
+        process::exit(1);
+    }
+
+    run_multi(days, release, false, false);
+}
+
+fn solve_day(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    alloc_stats: bool,
+    submit: Option<SubmitOptions>,
+    record: Option<&str>,
+    forward: &[String],
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,13 +73,35 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if alloc_stats {
+        cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+    }
+
+    if record.is_some_and(|record| record.ends_with(".gif")) {
+        cmd_args.extend(["--features".to_string(), "record-gif".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit) = &submit {
+        if !submit.force && !example_passes(day, submit.part) {
+            process::exit(1);
+        }
+
         cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+        cmd_args.push(submit.part.to_string());
+        if submit.dry_run {
+            cmd_args.push("--dry-run".to_string());
+        }
     }
 
+    if let Some(record) = record {
+        cmd_args.push("--record".to_string());
+        cmd_args.push(record.to_string());
+    }
+
+    cmd_args.extend(forward.iter().cloned());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -32,3 +111,114 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Checks the part against its example before submitting, prints why it does not pass.
+fn example_passes(day: Day, part: Part) -> bool {
+    println!("Checking part {part} against its example...");
+
+    match check_example(day, part) {
+        ExampleCheck::Passed => true,
+        ExampleCheck::Missing => {
+            eprintln!(
+                "No example test found for part {part}, not submitting. Add `test_part_{}` or append `--force` to submit anyway.",
+                match part {
+                    Part::One => "one",
+                    Part::Two => "two",
+                }
+            );
+            false
+        }
+        ExampleCheck::Failed(details) => {
+            eprintln!("{details}");
+            eprintln!("The tests compare the result (`left`) with the expected answer (`right`).");
+            eprintln!(
+                "The example of part {part} fails, not submitting. Fix the solution or its test, or append `--force` to submit anyway."
+            );
+            false
+        }
+    }
+}
+
+/// Runs the example test of a part, i.e. `tests::test_part_one` or `tests::test_part_two` of the day.
+fn check_example(day: Day, part: Part) -> ExampleCheck {
+    let test = match part {
+        Part::One => "tests::test_part_one",
+        Part::Two => "tests::test_part_two",
+    };
+
+    let output = Command::new("cargo")
+        .args([
+            "test",
+            "--quiet",
+            "--bin",
+            &day.to_string(),
+            "--",
+            "--exact",
+            test,
+        ])
+        .env("RUST_BACKTRACE", "0")
+        .output();
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => return ExampleCheck::Failed(format!("Failed to run the example test: {e}")),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if output.status.success() {
+        if stdout.contains("running 0 tests") {
+            ExampleCheck::Missing
+        } else {
+            ExampleCheck::Passed
+        }
+    } else {
+        // fall back to the build errors if the test did not run at all.
+        let details = failure_details(&stdout).map_or_else(
+            || String::from_utf8_lossy(&output.stderr).into_owned(),
+            str::to_string,
+        );
+        ExampleCheck::Failed(details)
+    }
+}
+
+/// Extracts the output of the failed test, e.g. the diff of a failed `assert_eq!`, from the output of `cargo test`.
+fn failure_details(stdout: &str) -> Option<&str> {
+    let start = stdout.find("---- ")?;
+    let details = &stdout[start..];
+    let end = details.find("\n\nfailures:").unwrap_or(details.len());
+    Some(details[..end].trim_end())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::failure_details;
+
+    #[test]
+    fn extracts_failure_details() {
+        let stdout = "
+running 1 test
+F
+failures:
+
+---- tests::test_part_one stdout ----
+
+thread 'tests::test_part_one' panicked at src/bin/13.rs:104:9:
+assertion `left == right` failed
+  left: Some(480)
+ right: Some(481)
+
+
+failures:
+    tests::test_part_one
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 2 filtered out; finished in 0.00s
+";
+        let details = failure_details(stdout).unwrap();
+        assert!(details.starts_with("---- tests::test_part_one stdout ----"));
+        assert!(details.ends_with(" right: Some(481)"));
+        assert_eq!(failure_details("running 1 test\n."), None);
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::status::{self, DayStatus, PartStatus};
+use crate::template::timings::Timings;
+use crate::template::{readme_status, ANSI_BOLD, ANSI_RESET, EVENT_DAYS};
+
+const COLUMNS: usize = 5;
+
+pub fn handle(store: bool) {
+    let answers = Answers::read_from_file();
+    let timings = Timings::read_from_file();
+    let statuses = status::collect(&answers, &timings, |day| {
+        Path::new(&get_path_for_bin(day)).exists()
+    });
+
+    print_grid(&statuses);
+
+    if store {
+        println!();
+        match readme_status::update(&statuses) {
+            Ok(()) => {
+                println!("Stored updated status.");
+            }
+            Err(_) => {
+                eprintln!("Failed to store updated status.");
+            }
+        }
+    }
+}
+
+fn format_cell(status: &DayStatus) -> String {
+    let day = if status.part_1 == PartStatus::Accepted && status.part_2 == PartStatus::Accepted {
+        format!("{ANSI_BOLD}{}{ANSI_RESET}", status.day)
+    } else {
+        status.day.to_string()
+    };
+
+    format!(
+        "{day} {}{} {:>7}",
+        status.part_1.symbol(),
+        status.part_2.symbol(),
+        status.total_time.as_deref().unwrap_or("")
+    )
+}
+
+fn print_grid(statuses: &[DayStatus]) {
+    for row in statuses.chunks(COLUMNS) {
+        let cells: Vec<String> = row.iter().map(format_cell).collect();
+        println!("{}", cells.join(" │ "));
+    }
+
+    println!();
+
+    let legend: Vec<String> = PartStatus::all()
+        .iter()
+        .map(|s| format!("{} {}", s.symbol(), s.description()))
+        .collect();
+    println!("{}", legend.join("  "));
+
+    println!(
+        "{ANSI_BOLD}Stars:{ANSI_RESET} {}/{}",
+        status::count_stars(statuses),
+        u32::from(EVENT_DAYS) * 2
+    );
+}
@@ -0,0 +1,86 @@
+use std::{
+    collections::HashSet,
+    io::{stdout, Write},
+    process,
+    time::Duration,
+};
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::unlock::{self, Clock, SystemClock};
+use crate::template::{aoc_cli, format_duration, Day, EVENT_DAYS};
+
+const DOWNLOAD_ATTEMPTS: u32 = 6;
+const DOWNLOAD_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+pub fn handle(wait: bool) {
+    let clock = SystemClock;
+
+    let day = if wait {
+        wait_for_next_day(&clock)
+    } else {
+        match unlock::today(&clock) {
+            Some(day) => day,
+            None => {
+                eprintln!(
+                    "`today` command can only be run between the 1st of december and \
+                    day {EVENT_DAYS} of the event. Please use `scaffold` with a specific day \
+                    or pass `--wait` to wait for the next puzzle."
+                );
+                process::exit(1);
+            }
+        }
+    };
+
+    let days = HashSet::from([day]);
+    scaffold::handle(&days, false);
+
+    if wait {
+        download_with_retries(&clock, day);
+    } else {
+        download::handle(&days);
+    }
+
+    read::handle(&days);
+}
+
+/// Counts down to the next unlock and returns the day that was unlocked.
+fn wait_for_next_day(clock: &impl Clock) -> Day {
+    let Some(unlock) = unlock::next_unlock(clock) else {
+        eprintln!("There are no more puzzles to unlock this year.");
+        process::exit(1);
+    };
+
+    let mut stdout = stdout();
+    unlock::wait_for_unlock(clock, &unlock, |remaining| {
+        print!(
+            "\r⏳ Day {} unlocks in {} ",
+            unlock.day,
+            format_duration(remaining)
+        );
+        let _ = stdout.flush();
+    });
+    println!("\r🎄 Day {} is unlocked!            ", unlock.day);
+
+    unlock.day
+}
+
+/// The input is not always available right at the unlock, so retry the download a few times.
+fn download_with_retries(clock: &impl Clock, day: Day) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let result =
+        unlock::retry_with_backoff(clock, DOWNLOAD_ATTEMPTS, DOWNLOAD_RETRY_DELAY, |attempt| {
+            if attempt > 1 {
+                println!("Retrying download (attempt {attempt}/{DOWNLOAD_ATTEMPTS})...");
+            }
+            aoc_cli::download(day)
+        });
+
+    if let Err(e) = result {
+        eprintln!("failed to call aoc-cli: {e}");
+        process::exit(1);
+    }
+}
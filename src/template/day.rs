@@ -3,12 +3,63 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use crate::template::unlock;
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+/// The number of days of the configured event.
+///
+/// Read from the `AOC_DAYS` environment variable at compile time. If it is not set, the length
+/// is derived from `AOC_YEAR`: events from 2025 onwards run for 12 days, earlier ones for 25.
+pub const EVENT_DAYS: u8 = event_days(option_env!("AOC_DAYS"), option_env!("AOC_YEAR"));
+
+const _ASSERT_EVENT_DAYS: () = assert!(
+    EVENT_DAYS != 0 && EVENT_DAYS <= 31,
+    "invalid `AOC_DAYS`, expecting a value between 1 and 31"
+);
+
+const fn event_days(days: Option<&str>, year: Option<&str>) -> u8 {
+    if let Some(days) = days {
+        return match parse_u16(days) {
+            #[allow(clippy::cast_possible_truncation)]
+            Some(days) if days <= u8::MAX as u16 => days as u8,
+            _ => 0,
+        };
+    }
+
+    match year {
+        Some(year) => match parse_u16(year) {
+            Some(year) if year >= 2025 => 12,
+            _ => 25,
+        },
+        None => 25,
+    }
+}
+
+const fn parse_u16(s: &str) -> Option<u16> {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        value = match value.checked_mul(10) {
+            Some(v) => match v.checked_add((bytes[i] - b'0') as u16) {
+                Some(v) => v,
+                None => return None,
+            },
+            None => return None,
+        };
+        i += 1;
+    }
+
+    Some(value)
+}
+
+/// A valid day number of advent (i.e. an integer in range 1 to [`EVENT_DAYS`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +76,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > EVENT_DAYS {
             return None;
         }
         Some(Self(day))
@@ -45,15 +96,10 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st of december and the last day of the event,
+    /// `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
+        unlock::today(&unlock::SystemClock)
     }
 }
 
@@ -94,18 +140,18 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {EVENT_DAYS}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of the event.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of the event.
 pub struct AllDays {
     current: u8,
 }
@@ -121,10 +167,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > EVENT_DAYS {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `EVENT_DAYS`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -139,11 +185,11 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::EVENT_DAYS,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and the number of event days"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,37 +200,24 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, event_days, Day, EVENT_DAYS};
+
+    #[test]
+    fn event_days_from_config() {
+        assert_eq!(event_days(Some("12"), Some("2024")), 12);
+        assert_eq!(event_days(None, Some("2025")), 12);
+        assert_eq!(event_days(None, Some("2024")), 25);
+        assert_eq!(event_days(None, None), 25);
+        assert_eq!(event_days(Some("twelve"), None), 0);
+    }
 
     #[test]
     fn all_days_iterator() {
         let mut iter = all_days();
 
-        assert_eq!(iter.next(), Some(Day(1)));
-        assert_eq!(iter.next(), Some(Day(2)));
-        assert_eq!(iter.next(), Some(Day(3)));
-        assert_eq!(iter.next(), Some(Day(4)));
-        assert_eq!(iter.next(), Some(Day(5)));
-        assert_eq!(iter.next(), Some(Day(6)));
-        assert_eq!(iter.next(), Some(Day(7)));
-        assert_eq!(iter.next(), Some(Day(8)));
-        assert_eq!(iter.next(), Some(Day(9)));
-        assert_eq!(iter.next(), Some(Day(10)));
-        assert_eq!(iter.next(), Some(Day(11)));
-        assert_eq!(iter.next(), Some(Day(12)));
-        assert_eq!(iter.next(), Some(Day(13)));
-        assert_eq!(iter.next(), Some(Day(14)));
-        assert_eq!(iter.next(), Some(Day(15)));
-        assert_eq!(iter.next(), Some(Day(16)));
-        assert_eq!(iter.next(), Some(Day(17)));
-        assert_eq!(iter.next(), Some(Day(18)));
-        assert_eq!(iter.next(), Some(Day(19)));
-        assert_eq!(iter.next(), Some(Day(20)));
-        assert_eq!(iter.next(), Some(Day(21)));
-        assert_eq!(iter.next(), Some(Day(22)));
-        assert_eq!(iter.next(), Some(Day(23)));
-        assert_eq!(iter.next(), Some(Day(24)));
-        assert_eq!(iter.next(), Some(Day(25)));
+        for day in 1..=EVENT_DAYS {
+            assert_eq!(iter.next(), Some(Day(day)));
+        }
         assert_eq!(iter.next(), None);
     }
 }
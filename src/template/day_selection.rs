@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day};
+
+/// A set of days selected on the command line.
+///
+/// Selectors are either a single day (`5`), an inclusive range (`1-5`), a comma separated
+/// list of days and ranges (`12,14,16` or `1-3,7`), `all` or `unsolved`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    /// Every day of advent.
+    All,
+    /// Every day that does not have stored timings for both parts yet.
+    Unsolved,
+    /// An explicit set of days.
+    Days(HashSet<Day>),
+}
+
+impl DaySelection {
+    /// Resolves the selection into the set of days it refers to.
+    pub fn days(&self) -> HashSet<Day> {
+        match self {
+            Self::All => all_days().collect(),
+            Self::Unsolved => {
+                let stored_timings = Timings::read_from_file();
+                all_days()
+                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .collect()
+            }
+            Self::Days(days) => days.clone(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => return Ok(Self::All),
+            "unsolved" => return Ok(Self::Unsolved),
+            _ => {}
+        }
+
+        let mut days = HashSet::new();
+
+        for token in s.split(',') {
+            let err = || DaySelectionFromStrError(token.to_string());
+
+            match token.split_once('-') {
+                Some((start, end)) => {
+                    let start: Day = start.parse().map_err(|_| err())?;
+                    let end: Day = end.parse().map_err(|_| err())?;
+                    if start > end {
+                        return Err(err());
+                    }
+                    days.extend(all_days().filter(|day| *day >= start && *day <= end));
+                }
+                None => {
+                    days.insert(token.parse().map_err(|_| err())?);
+                }
+            }
+        }
+
+        Ok(Self::Days(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError(String);
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selector `{}`, expecting a day (`5`), a range (`1-5`), a list (`12,14,16`), `all` or `unsolved`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::DaySelection;
+    use crate::{day, template::EVENT_DAYS};
+
+    #[test]
+    fn parses_single_days() {
+        let selection: DaySelection = "5".parse().unwrap();
+        assert_eq!(selection, DaySelection::Days(HashSet::from([day!(5)])));
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let selection: DaySelection = "2-5".parse().unwrap();
+        assert_eq!(
+            selection.days(),
+            HashSet::from([day!(2), day!(3), day!(4), day!(5)])
+        );
+
+        let selection: DaySelection = format!("1-{EVENT_DAYS}").parse().unwrap();
+        assert_eq!(selection.days(), DaySelection::All.days());
+    }
+
+    #[test]
+    fn parses_lists() {
+        let selection: DaySelection = "2,4,6".parse().unwrap();
+        assert_eq!(selection.days(), HashSet::from([day!(2), day!(4), day!(6)]));
+
+        let selection: DaySelection = "1-3,7,2".parse().unwrap();
+        assert_eq!(
+            selection.days(),
+            HashSet::from([day!(1), day!(2), day!(3), day!(7)])
+        );
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!("all".parse::<DaySelection>().unwrap(), DaySelection::All);
+        assert_eq!(
+            "unsolved".parse::<DaySelection>().unwrap(),
+            DaySelection::Unsolved
+        );
+        assert_eq!(DaySelection::All.days().len(), EVENT_DAYS as usize);
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        assert!("".parse::<DaySelection>().is_err());
+        assert!((EVENT_DAYS + 1)
+            .to_string()
+            .parse::<DaySelection>()
+            .is_err());
+        assert!(format!("1-{}", EVENT_DAYS + 1)
+            .parse::<DaySelection>()
+            .is_err());
+        assert!("5-1".parse::<DaySelection>().is_err());
+        assert!("1-".parse::<DaySelection>().is_err());
+        assert!("1,,2".parse::<DaySelection>().is_err());
+        assert!("some".parse::<DaySelection>().is_err());
+    }
+}
@@ -0,0 +1,358 @@
+/// Private leaderboard data, parsed from the JSON export of a leaderboard.
+/// The export is available at `https://adventofcode.com/{year}/leaderboard/private/view/{id}.json`.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{all_days, Day, Part};
+
+pub static LEADERBOARD_FILE_PATH: &str = "./data/leaderboard.json";
+
+/// Puzzles unlock at midnight UTC-5, i.e. at 05:00 UTC.
+const UNLOCK_HOUR_UTC: i64 = 5;
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    /// Unix timestamps of the stars a member earned, keyed by day and part.
+    pub completions: HashMap<(Day, Part), i64>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Seconds between the unlock of a day and the star of a part.
+    pub fn solve_time(&self, year: i32, day: Day, part: Part) -> Option<i64> {
+        self.completions
+            .get(&(day, part))
+            .map(|ts| ts - unlock_timestamp(year, day))
+    }
+
+    /// Seconds between the first and the second star of a day.
+    pub fn part_delta(&self, day: Day) -> Option<i64> {
+        let part_1 = self.completions.get(&(day, Part::One))?;
+        let part_2 = self.completions.get(&(day, Part::Two))?;
+        Some(part_2 - part_1)
+    }
+}
+
+/// A private leaderboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leaderboard {
+    pub year: i32,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// Reads a leaderboard export from a file.
+    pub fn read_from_file(path: &str) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|e| format!("could not read \"{path}\": {e}"))
+            .and_then(Leaderboard::try_from)
+    }
+
+    /// Members ordered by local score, then by stars and by who got their last star first.
+    pub fn rankings(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    /// Days on which at least one member earned a star.
+    pub fn days_with_stars(&self) -> Vec<Day> {
+        all_days()
+            .filter(|day| {
+                self.members
+                    .iter()
+                    .any(|m| m.completions.contains_key(&(*day, Part::One)))
+            })
+            .collect()
+    }
+}
+
+/// Download the JSON export of a private leaderboard to [`LEADERBOARD_FILE_PATH`].
+///
+/// Uses `curl` with the session cookie that is also used by aoc-cli, read from
+/// `ADVENT_OF_CODE_SESSION` or `~/.adventofcode.session`.
+pub fn download(year: u16, id: u64) -> Result<(), String> {
+    let session = read_session()
+        .ok_or("no session cookie found in ADVENT_OF_CODE_SESSION or ~/.adventofcode.session.")?;
+
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+
+    // The session is passed on stdin so that it does not show up in the process list.
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--header",
+            "@-",
+            "--output",
+            LEADERBOARD_FILE_PATH,
+            &url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|_| "curl could not be called.")?;
+
+    child
+        .stdin
+        .take()
+        .ok_or("curl could not be called.")?
+        .write_all(format!("Cookie: session={session}\n").as_bytes())
+        .map_err(|_| "could not pass the session cookie to curl.")?;
+
+    let status = child.wait().map_err(|_| "curl could not be called.")?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("could not download leaderboard from {url}."))
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let path = PathBuf::from(home).join(".adventofcode.session");
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Unix timestamp of the unlock of a puzzle.
+pub fn unlock_timestamp(year: i32, day: Day) -> i64 {
+    days_from_civil(year, 12, u32::from(day.into_inner())) * 86_400 + UNLOCK_HOUR_UTC * 3600
+}
+
+/// Days since the unix epoch for a date in the proleptic gregorian calendar.
+/// See: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(if month <= 2 { year - 1 } else { year });
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn get_number(json: &HashMap<String, JsonValue>, key: &str) -> Option<f64> {
+    json.get(key).and_then(|v| v.get::<f64>()).copied()
+}
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| v.parse().ok())
+            .ok_or("expected `json.event` to be a year.")?;
+
+        let members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Leaderboard { year, members })
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let id = get_number(json, "id").ok_or("Expected member.id to be a number.")? as u64;
+
+        let name = json
+            .get("name")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected member.name to be null or string.")?
+            .cloned();
+
+        let stars =
+            get_number(json, "stars").ok_or("Expected member.stars to be a number.")? as u32;
+
+        let local_score = get_number(json, "local_score")
+            .ok_or("Expected member.local_score to be a number.")? as u32;
+
+        let last_star_ts = get_number(json, "last_star_ts").unwrap_or_default() as i64;
+
+        let mut completions = HashMap::new();
+
+        let days = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        for (day, parts) in days {
+            // stars on days beyond the configured event length are left out.
+            let Ok(day) = Day::from_str(day) else {
+                continue;
+            };
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected completion day to be an object.")?;
+
+            for (part, star) in parts {
+                let part = Part::from_str(part).map_err(|e| e.to_string())?;
+                let ts = star
+                    .get::<HashMap<String, JsonValue>>()
+                    .and_then(|star| get_number(star, "get_star_ts"))
+                    .ok_or("Expected star.get_star_ts to be a number.")?;
+                completions.insert((day, part), ts as i64);
+            }
+        }
+
+        Ok(Member {
+            id,
+            name,
+            stars,
+            local_score,
+            last_star_ts,
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+pub(crate) mod tests {
+    use super::{unlock_timestamp, Leaderboard};
+    use crate::{
+        day,
+        template::{Part, EVENT_DAYS},
+    };
+
+    pub fn get_mock_leaderboard() -> Leaderboard {
+        let json = r#"{
+            "owner_id": 1,
+            "event": "2024",
+            "members": {
+                "1": {
+                    "id": 1, "name": "alice", "stars": 4, "local_score": 10, "global_score": 0,
+                    "last_star_ts": 1733119000,
+                    "completion_day_level": {
+                        "1": { "1": { "get_star_ts": 1733029800, "star_index": 0 }, "2": { "get_star_ts": 1733030100, "star_index": 1 } },
+                        "2": { "1": { "get_star_ts": 1733117400, "star_index": 2 }, "2": { "get_star_ts": 1733119000, "star_index": 3 } }
+                    }
+                },
+                "2": {
+                    "id": 2, "name": null, "stars": 1, "local_score": 3, "global_score": 0,
+                    "last_star_ts": 1733040000,
+                    "completion_day_level": {
+                        "1": { "1": { "get_star_ts": 1733040000, "star_index": 4 } }
+                    }
+                }
+            }
+        }"#;
+        Leaderboard::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn computes_unlock_timestamps() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_timestamp(2024, day!(1)), 1_733_029_200);
+        // 2015-12-02T05:00:00Z
+        assert_eq!(unlock_timestamp(2015, day!(2)), 1_449_032_400);
+    }
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(leaderboard.year, 2024);
+        assert_eq!(leaderboard.members.len(), 2);
+
+        let rankings = leaderboard.rankings();
+        assert_eq!(rankings[0].display_name(), "alice");
+        assert_eq!(rankings[1].display_name(), "(anonymous user #2)");
+        assert_eq!(leaderboard.days_with_stars(), vec![day!(1), day!(2)]);
+    }
+
+    #[test]
+    fn computes_solve_times() {
+        let leaderboard = get_mock_leaderboard();
+        let alice = leaderboard.rankings()[0];
+        assert_eq!(alice.solve_time(2024, day!(1), Part::One), Some(600));
+        assert_eq!(alice.solve_time(2024, day!(1), Part::Two), Some(900));
+        assert_eq!(alice.part_delta(day!(1)), Some(300));
+
+        let anonymous = leaderboard.rankings()[1];
+        assert_eq!(anonymous.solve_time(2024, day!(1), Part::Two), None);
+        assert_eq!(anonymous.part_delta(day!(1)), None);
+    }
+
+    #[test]
+    fn skips_days_beyond_the_event() {
+        let json = format!(
+            r#"{{
+                "event": "2024",
+                "members": {{
+                    "1": {{
+                        "id": 1, "name": "alice", "stars": 2, "local_score": 4, "last_star_ts": 1733030100,
+                        "completion_day_level": {{
+                            "1": {{ "1": {{ "get_star_ts": 1733029800 }} }},
+                            "{}": {{ "1": {{ "get_star_ts": 1733030100 }} }}
+                        }}
+                    }}
+                }}
+            }}"#,
+            EVENT_DAYS + 1
+        );
+        let leaderboard = Leaderboard::try_from(json).unwrap();
+        assert_eq!(leaderboard.members[0].completions.len(), 1);
+        assert_eq!(leaderboard.days_with_stars(), vec![day!(1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_leaderboard() {
+        Leaderboard::try_from(r#"{ "event": "2024" }"#.to_string()).unwrap();
+    }
+}
@@ -1,14 +1,30 @@
-use std::{env, fs};
+use std::{env, fs, time::Duration};
 
+pub mod alloc_stats;
 pub mod aoc_cli;
 pub mod commands;
+pub mod recorder;
 pub mod runner;
+#[cfg(feature = "today")]
+pub mod unlock;
 
+pub use args::*;
 pub use day::*;
+pub use day_selection::*;
+pub use part::*;
 
+mod answers;
+mod args;
 mod day;
+mod day_selection;
+mod leaderboard;
+mod part;
 mod readme_benchmarks;
+mod readme_leaderboard;
+mod readme_status;
 mod run_multi;
+mod status;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -26,7 +42,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: Day, part: Part) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
@@ -36,22 +52,47 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
+/// Formats a duration as `hh:mm:ss`, prefixed with the number of days if there are any.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        (secs % 86_400) / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// `extras = <function>` registers a function that is called with the input once both parts have run.
+/// It is meant for things like visualizations that are enabled with [`solution_flag`] or [`solution_arg`],
+/// and that should neither be benchmarked nor run by tests.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, One] [part_two, Two]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, One]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, Two]);
+    };
+    ($day:expr, extras = $extras:path) => {
+        $crate::solution!(@impl $day, [part_one, One] [part_two, Two] extras: $extras);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$func:expr, $part:ident] )* $( extras: $extras:path )?) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +100,34 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(feature = "alloc-stats")]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc_stats::CountingAlloc =
+            $crate::template::alloc_stats::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
+            $crate::template::recorder::start_from_args();
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $( run_part(|input: &str| $func(input.into()), input.as_str(), DAY, $crate::template::Part::$part); )*
+            $( $extras(input.as_str()); )?
+            $crate::template::recorder::finish();
         }
     };
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_duration;
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_duration(Duration::from_secs(3_723)), "01:02:03");
+        assert_eq!(format_duration(Duration::from_secs(90_061)), "1d 01:01:01");
+    }
+}
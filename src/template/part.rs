@@ -0,0 +1,93 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A part of a day's puzzle (i.e. either part one or part two).
+///
+/// # Display
+/// This value displays as the part number.
+///
+/// ```
+/// # use advent_of_code::template::Part;
+/// assert_eq!(Part::Two.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Creates a [`Part`] from the provided value if it's either 1 or 2,
+    /// returns [`None`] otherwise.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into an [`u8`].
+    pub fn into_inner(self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number of either 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Part;
+
+    #[test]
+    fn parses_valid_parts() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+    }
+
+    #[test]
+    fn rejects_invalid_parts() {
+        assert!("0".parse::<Part>().is_err());
+        assert!("3".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn displays_as_number() {
+        assert_eq!(Part::One.to_string(), "1");
+        assert_eq!(Part::Two.to_string(), "2");
+    }
+}
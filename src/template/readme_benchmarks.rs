@@ -21,8 +21,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
@@ -30,8 +30,8 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,6 +52,22 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// Replaces the table between the two occurrences of `marker`, including the markers, with `table`.
+pub fn replace_table(readme: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(readme, marker)?;
+    readme.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
+/// Reads the readme, lets `update` replace one of its tables and writes it back.
+pub fn update_readme(update: impl FnOnce(&mut String) -> Result<(), Error>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update(&mut readme)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
@@ -82,19 +98,12 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 }
 
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+    replace_table(s, MARKER, &construct_table("##", timings, total_millis))
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+    update_readme(|readme| update_content(readme, timings, total_millis))
 }
 
 #[cfg(feature = "test_lib")]
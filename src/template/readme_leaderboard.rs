@@ -0,0 +1,97 @@
+/// Module that updates the leaderboard table of the readme: the rank, name, stars and local score of every
+/// member of a private leaderboard.
+use crate::template::leaderboard::Leaderboard;
+use crate::template::readme_benchmarks::{replace_table, update_readme, Error};
+
+static MARKER: &str = "<!--- leaderboard table --->";
+
+fn construct_table(prefix: &str, leaderboard: &Leaderboard) -> String {
+    let header = format!("{prefix} Leaderboard {}", leaderboard.year);
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Rank | Member | Stars | Score |".into(),
+        "| :---: | :--- | :---: | :---: |".into(),
+    ];
+
+    for (rank, member) in leaderboard.rankings().into_iter().enumerate() {
+        lines.push(format!(
+            "| {} | {} | {} | {} |",
+            rank + 1,
+            escape_markdown(&member.display_name()),
+            member.stars,
+            member.local_score
+        ));
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// Escapes the characters of a member name that would break the table or be read as markdown.
+fn escape_markdown(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for ch in name.chars() {
+        if matches!(
+            ch,
+            '\\' | '|' | '`' | '*' | '_' | '~' | '[' | ']' | '<' | '>'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn update_content(s: &mut String, leaderboard: &Leaderboard) -> Result<(), Error> {
+    replace_table(s, MARKER, &construct_table("##", leaderboard))
+}
+
+pub fn update(leaderboard: &Leaderboard) -> Result<(), Error> {
+    update_readme(|readme| update_content(readme, leaderboard))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{escape_markdown, update_content, MARKER};
+    use crate::template::leaderboard::tests::get_mock_leaderboard;
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_leaderboard()).unwrap();
+    }
+
+    #[test]
+    fn format_leaderboard() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_leaderboard()).unwrap();
+        update_content(&mut s, &get_mock_leaderboard()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- leaderboard table --->",
+            "## Leaderboard 2024",
+            "",
+            "| Rank | Member | Stars | Score |",
+            "| :---: | :--- | :---: | :---: |",
+            "| 1 | alice | 4 | 10 |",
+            "| 2 | (anonymous user #2) | 1 | 3 |",
+            "<!--- leaderboard table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn escapes_member_names() {
+        assert_eq!(escape_markdown("alice"), "alice");
+        assert_eq!(escape_markdown("a|b"), "a\\|b");
+        assert_eq!(escape_markdown("__bold__"), "\\_\\_bold\\_\\_");
+        assert_eq!(escape_markdown("<img>"), "\\<img\\>");
+    }
+}
@@ -0,0 +1,131 @@
+/// Module that updates the status table of the readme: the state of both parts of every scaffolded day,
+/// its total benchmark time and the number of stars.
+use crate::template::readme_benchmarks::{get_path_for_bin, replace_table, update_readme, Error};
+use crate::template::status::{count_stars, DayStatus, PartStatus};
+use crate::template::EVENT_DAYS;
+
+static MARKER: &str = "<!--- status table --->";
+
+fn format_part(status: PartStatus) -> String {
+    match status {
+        PartStatus::Accepted => "⭐".into(),
+        status => status.symbol().to_string(),
+    }
+}
+
+fn construct_table(prefix: &str, statuses: &[DayStatus]) -> String {
+    let header = format!("{prefix} Status");
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 | Time |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for status in statuses.iter().filter(|s| s.is_scaffolded) {
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} | `{}` |",
+            status.day.into_inner(),
+            get_path_for_bin(status.day),
+            format_part(status.part_1),
+            format_part(status.part_2),
+            status.total_time.as_deref().unwrap_or("-"),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Stars: {}/{}**",
+        count_stars(statuses),
+        u32::from(EVENT_DAYS) * 2
+    ));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, statuses: &[DayStatus]) -> Result<(), Error> {
+    replace_table(s, MARKER, &construct_table("##", statuses))
+}
+
+pub fn update(statuses: &[DayStatus]) -> Result<(), Error> {
+    update_readme(|readme| update_content(readme, statuses))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::{
+        day,
+        template::{
+            status::{DayStatus, PartStatus},
+            EVENT_DAYS,
+        },
+    };
+
+    fn get_mock_statuses() -> Vec<DayStatus> {
+        vec![
+            DayStatus {
+                day: day!(1),
+                is_scaffolded: true,
+                part_1: PartStatus::Accepted,
+                part_2: PartStatus::Submitted,
+                total_time: Some("1.2ms".into()),
+            },
+            DayStatus {
+                day: day!(2),
+                is_scaffolded: false,
+                part_1: PartStatus::NotScaffolded,
+                part_2: PartStatus::NotScaffolded,
+                total_time: None,
+            },
+            DayStatus {
+                day: day!(3),
+                is_scaffolded: true,
+                part_1: PartStatus::Solved,
+                part_2: PartStatus::Unsolved,
+                total_time: None,
+            },
+        ]
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_statuses()).unwrap();
+    }
+
+    #[test]
+    fn updates_existing_status() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_statuses()).unwrap();
+        update_content(&mut s, &get_mock_statuses()).unwrap();
+        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.matches("## Status").collect::<Vec<&str>>().len(), 1);
+    }
+
+    #[test]
+    fn format_status() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_statuses()).unwrap();
+        let expected = [
+            "foo".to_string(),
+            "<!--- status table --->".into(),
+            "## Status".into(),
+            "".into(),
+            "| Day | Part 1 | Part 2 | Time |".into(),
+            "| :---: | :---: | :---: | :---: |".into(),
+            "| [Day 1](./src/bin/01.rs) | ⭐ | ☆ | `1.2ms` |".into(),
+            "| [Day 3](./src/bin/03.rs) | ○ | ✖ | `-` |".into(),
+            "".into(),
+            format!("**Stars: 1/{}**", u32::from(EVENT_DAYS) * 2),
+            "<!--- status table --->".into(),
+            "baz".into(),
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,342 @@
+/// Records the frames of a simulation to an animation file.
+/// Enabled with `cargo solve <day> --record <file>`, solutions push frames with [`push_frame`].
+///
+/// The format is picked by the file extension:
+///  - `.gif`: an animated gif, needs the `record-gif` feature.
+///  - `.cast`: an [asciicast](https://docs.asciinema.org/manual/asciicast/v2/) that can be replayed with `asciinema play`.
+///  - anything else: a file of ANSI escaped frames that can be replayed with `cat`.
+use std::collections::HashMap;
+use std::fmt::{Display, Write as _};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tinyjson::JsonValue;
+
+use crate::grid::Grid;
+use crate::image::Rgb;
+use crate::template::solution_arg;
+
+/// Display time of a single frame.
+const FRAME_DELAY_MS: u16 = 50;
+/// Size of a grid cell in gif frames, in pixels.
+#[cfg(feature = "record-gif")]
+const GIF_CELL_SIZE: usize = 4;
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+enum Output {
+    #[cfg(feature = "record-gif")]
+    Gif(gif::Encoder<BufWriter<File>>),
+    Asciicast {
+        writer: BufWriter<File>,
+        time: f64,
+    },
+    Ansi(BufWriter<File>),
+}
+
+/// A single frame: the character and the colour of every cell.
+#[derive(PartialEq)]
+struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<(char, Rgb)>,
+}
+
+struct Recorder {
+    path: PathBuf,
+    output: Option<Output>,
+    /// A new file is started whenever the size of the frames changes, e.g. between parts.
+    segment: usize,
+    last_frame: Option<Frame>,
+    frames: usize,
+}
+
+/// Whether frames are being recorded. Can be used to skip building frames that would be discarded.
+pub fn is_recording() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Records a frame, rendering every cell with its `Display` implementation and colouring it with `palette`.
+/// Does nothing if no recording was requested. Frames that equal the previous frame are skipped.
+pub fn push_frame<T: Display>(grid: &Grid<T>, palette: impl Fn(char) -> Rgb) {
+    if !is_recording() {
+        return;
+    }
+
+    let cells = grid
+        .iter()
+        .map(|(_, cell)| {
+            let ch = cell.to_string().chars().next().unwrap_or(' ');
+            (ch, palette(ch))
+        })
+        .collect();
+
+    let frame = Frame {
+        width: grid.width(),
+        height: grid.height(),
+        cells,
+    };
+
+    if let Some(recorder) = RECORDER.lock().unwrap().as_mut() {
+        if let Err(e) = recorder.push(frame) {
+            eprintln!("Failed to record frame: {e}");
+            ACTIVE.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Starts a recording if the solution was called with `--record <file>`.
+pub fn start_from_args() {
+    let Some(Some(path)) = solution_arg("--record") else {
+        return;
+    };
+
+    if cfg!(not(feature = "record-gif")) && path.ends_with(".gif") {
+        eprintln!("Recording gifs needs the `record-gif` feature, e.g. `cargo run --features record-gif --bin <day> -- --record {path}`.");
+        return;
+    }
+
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        path: PathBuf::from(path),
+        output: None,
+        segment: 0,
+        last_frame: None,
+        frames: 0,
+    });
+    ACTIVE.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording frames, e.g. while a solution is benchmarked.
+pub(crate) fn pause() {
+    ACTIVE.store(false, Ordering::Relaxed);
+}
+
+pub(crate) fn resume() {
+    ACTIVE.store(RECORDER.lock().unwrap().is_some(), Ordering::Relaxed);
+}
+
+/// Finishes the recording and writes all remaining data to disk.
+pub fn finish() {
+    ACTIVE.store(false, Ordering::Relaxed);
+    if let Some(mut recorder) = RECORDER.lock().unwrap().take() {
+        let frames = recorder.frames;
+        match recorder.close() {
+            Ok(()) if frames > 0 => eprintln!("Recorded {frames} frames."),
+            Ok(()) => eprintln!("No frames were recorded, does this solution push frames?"),
+            Err(e) => eprintln!("Failed to write recording: {e}"),
+        }
+    }
+}
+
+impl Recorder {
+    fn push(&mut self, frame: Frame) -> std::io::Result<()> {
+        if self.last_frame.as_ref() == Some(&frame) {
+            return Ok(());
+        }
+
+        let size_changed = self
+            .last_frame
+            .as_ref()
+            .is_some_and(|last| (last.width, last.height) != (frame.width, frame.height));
+
+        if self.output.is_none() || size_changed {
+            self.close()?;
+            self.segment += 1;
+            let path = segment_path(&self.path, self.segment);
+            eprintln!("Recording to {}", path.display());
+            self.output = Some(open(&path, &frame)?);
+        }
+
+        match self.output.as_mut().unwrap() {
+            #[cfg(feature = "record-gif")]
+            Output::Gif(encoder) => encoder
+                .write_frame(&gif_frame(&frame))
+                .map_err(std::io::Error::other)?,
+            Output::Asciicast { writer, time } => {
+                let event = JsonValue::Array(vec![
+                    JsonValue::Number(*time),
+                    JsonValue::String("o".into()),
+                    JsonValue::String(ansi_frame(&frame)),
+                ]);
+                writeln!(writer, "{}", event.stringify().unwrap())?;
+                *time += f64::from(FRAME_DELAY_MS) / 1000.0;
+            }
+            Output::Ansi(writer) => write!(writer, "{}", ansi_frame(&frame))?,
+        }
+
+        self.frames += 1;
+        self.last_frame = Some(frame);
+        Ok(())
+    }
+
+    fn close(&mut self) -> std::io::Result<()> {
+        match self.output.take() {
+            #[cfg(feature = "record-gif")]
+            Some(Output::Gif(encoder)) => {
+                encoder.into_inner().map_err(std::io::Error::other)?.flush()
+            }
+            Some(Output::Asciicast { mut writer, .. } | Output::Ansi(mut writer)) => {
+                write!(writer, "\x1b[0m")?;
+                writer.flush()
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// The path of the n-th file of a recording, e.g. `out.gif`, `out-2.gif`, ...
+fn segment_path(path: &Path, segment: usize) -> PathBuf {
+    if segment <= 1 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{segment}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{segment}"),
+    };
+    path.with_file_name(name)
+}
+
+fn open(path: &Path, frame: &Frame) -> std::io::Result<Output> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "record-gif")]
+        Some("gif") => {
+            let (width, height) = gif_size(frame)?;
+            let mut encoder =
+                gif::Encoder::new(writer, width, height, &[]).map_err(std::io::Error::other)?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(std::io::Error::other)?;
+            Ok(Output::Gif(encoder))
+        }
+        Some("cast") => {
+            let header = JsonValue::Object(HashMap::from([
+                ("version".into(), JsonValue::Number(2.0)),
+                #[allow(clippy::cast_precision_loss)]
+                ("width".into(), JsonValue::Number(frame.width as f64)),
+                #[allow(clippy::cast_precision_loss)]
+                ("height".into(), JsonValue::Number(frame.height as f64)),
+            ]));
+            writeln!(writer, "{}", header.stringify().unwrap())?;
+            Ok(Output::Asciicast { writer, time: 0.0 })
+        }
+        _ => {
+            write!(writer, "\x1b[2J")?;
+            Ok(Output::Ansi(writer))
+        }
+    }
+}
+
+#[cfg(feature = "record-gif")]
+fn gif_size(frame: &Frame) -> std::io::Result<(u16, u16)> {
+    let size = |cells: usize| u16::try_from(cells * GIF_CELL_SIZE).map_err(std::io::Error::other);
+    Ok((size(frame.width)?, size(frame.height)?))
+}
+
+/// Converts a frame to a gif frame with a local palette of the colours it uses.
+#[cfg(feature = "record-gif")]
+fn gif_frame(frame: &Frame) -> gif::Frame<'static> {
+    let mut palette: Vec<Rgb> = vec![];
+    let mut indices = Vec::with_capacity(frame.cells.len());
+
+    for (_, colour) in &frame.cells {
+        let index = palette.iter().position(|c| c == colour).unwrap_or_else(|| {
+            palette.push(*colour);
+            palette.len() - 1
+        });
+        // gif palettes are limited to 256 colours, further colours are drawn with the last one.
+        #[allow(clippy::cast_possible_truncation)]
+        indices.push(index.min(255) as u8);
+    }
+
+    let width = frame.width * GIF_CELL_SIZE;
+    let mut pixels = Vec::with_capacity(indices.len() * GIF_CELL_SIZE * GIF_CELL_SIZE);
+    for row in indices.chunks(frame.width.max(1)) {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|index| [*index; GIF_CELL_SIZE])
+            .collect();
+        for _ in 0..GIF_CELL_SIZE {
+            pixels.extend_from_slice(&line);
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    gif::Frame {
+        width: width as u16,
+        height: (frame.height * GIF_CELL_SIZE) as u16,
+        buffer: std::borrow::Cow::Owned(pixels),
+        palette: Some(palette.into_iter().take(256).flatten().collect()),
+        delay: FRAME_DELAY_MS / 10,
+        ..gif::Frame::default()
+    }
+}
+
+/// Draws a frame as coloured characters, starting at the top left corner of the terminal.
+fn ansi_frame(frame: &Frame) -> String {
+    let mut out = String::from("\x1b[H");
+    for row in frame.cells.chunks(frame.width.max(1)) {
+        let mut colour = None;
+        for (ch, rgb) in row {
+            if colour != Some(rgb) {
+                let [r, g, b] = rgb;
+                let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+                colour = Some(rgb);
+            }
+            out.push(*ch);
+        }
+        out.push_str("\x1b[0m\r\n");
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    #[cfg(feature = "record-gif")]
+    use super::gif_frame;
+    use super::{ansi_frame, segment_path, Frame};
+
+    fn get_mock_frame() -> Frame {
+        Frame {
+            width: 2,
+            height: 1,
+            cells: vec![('#', [255, 0, 0]), ('.', [0, 0, 0])],
+        }
+    }
+
+    #[test]
+    fn names_segments() {
+        let path = Path::new("out/day.gif");
+        assert_eq!(segment_path(path, 1), PathBuf::from("out/day.gif"));
+        assert_eq!(segment_path(path, 2), PathBuf::from("out/day-2.gif"));
+        assert_eq!(segment_path(Path::new("day"), 3), PathBuf::from("day-3"));
+    }
+
+    #[test]
+    fn draws_ansi_frames() {
+        assert_eq!(
+            ansi_frame(&get_mock_frame()),
+            "\x1b[H\x1b[38;2;255;0;0m#\x1b[38;2;0;0;0m.\x1b[0m\r\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "record-gif")]
+    fn scales_gif_frames() {
+        let frame = gif_frame(&get_mock_frame());
+        assert_eq!((frame.width, frame.height), (8, 4));
+        assert_eq!(frame.palette, Some(vec![255, 0, 0, 0, 0, 0]));
+        assert_eq!(&frame.buffer[..8], &[0, 0, 0, 0, 1, 1, 1, 1]);
+        assert_eq!(frame.buffer.len(), 32);
+    }
+}
@@ -7,8 +7,14 @@ use super::{
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    fresh_process: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut leaking_days: Vec<Day> = vec![];
 
     let mut need_space = false;
 
@@ -24,13 +30,18 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, fresh_process).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
+
+                if child_commands::has_state_leak(&output) {
+                    leaking_days.push(day);
+                }
             }
         });
 
@@ -40,6 +51,15 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
+
+        if !leaking_days.is_empty() {
+            let days: Vec<String> = leaking_days.iter().map(ToString::to_string).collect();
+            println!(
+                "\n{ANSI_BOLD}Warning:{ANSI_RESET} later samples of day(s) {} ran more than 10x faster than their first run. \
+                 These solutions likely keep state between calls, `--fresh-process` measures them honestly.",
+                days.join(", ")
+            );
+        }
         Some(timings)
     } else {
         None
@@ -68,7 +88,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{runner::STATE_LEAK_WARNING, Day};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -77,7 +97,12 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        fresh_process: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -94,6 +119,10 @@ pub mod child_commands {
             // mirror `--time` flag to child invocations.
             args.push("--");
             args.push("--time");
+
+            if fresh_process {
+                args.push("--fresh-process");
+            }
         }
 
         // spawn child command with piped stdout/stderr.
@@ -164,6 +193,11 @@ pub mod child_commands {
         timings
     }
 
+    /// Whether the runner flagged one of the parts as keeping state between benchmark samples.
+    pub fn has_state_leak(output: &[String]) -> bool {
+        output.iter().any(|line| line.contains(STATE_LEAK_WARNING))
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,7 +208,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +239,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{has_state_leak, parse_exec_time};
 
         use crate::day;
 
@@ -239,6 +273,20 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn ignores_state_leak_warnings() {
+            let output = [
+                "Part 1: 42 (306.0ns @ 10000 samples)".into(),
+                "Part 1: state leak suspected, the first run took 2.0s but later runs 306.0ns on average.".into(),
+                "Part 2: 10 (1.0ms @ 1000 samples)".into(),
+            ];
+            let res = parse_exec_time(&output, day!(11));
+            assert_eq!(res.part_1.unwrap(), "306.0ns");
+            assert_eq!(res.part_2.unwrap(), "1.0ms");
+            assert!(has_state_leak(&output));
+            assert!(!has_state_leak(&output[2..]));
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -2,34 +2,99 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::process::{Command, Stdio};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::answer::Answer;
+use crate::template::alloc_stats::{self, AllocStats};
+use crate::template::answers::Answers;
+use crate::template::recorder;
+use crate::template::submissions::{Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, Part, ANSI_ITALIC, ANSI_RESET};
+
+/// Set on the processes started by `--fresh-process`, holds the part that should be sampled.
+const FRESH_SAMPLE_VAR: &str = "AOC_FRESH_SAMPLE";
+
+/// Later samples being this much faster than the first run hints at state kept between calls.
+const STATE_LEAK_FACTOR: u32 = 10;
+
+/// Printed by the runner when a solution is suspected to keep state between calls.
+pub const STATE_LEAK_WARNING: &str = "state leak suspected";
+
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: Part,
+) {
+    if let Ok(sample_part) = env::var(FRESH_SAMPLE_VAR) {
+        run_fresh_sample(func, input, part, &sample_part);
+        return;
+    }
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples, alloc_stats, first_run) = run_timed(
+        |input| func(input).map(|value| Answer::parse(&value.to_string())),
+        input,
+        part,
+        |result| print_result(result, &part_str, ""),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut duration_str = format_duration(&duration, samples);
+    if let Some(alloc_stats) = alloc_stats {
+        duration_str.push_str(&format!(" [{alloc_stats}]"));
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    print_result(&result, &part_str, &duration_str);
+
+    if samples > 1 && first_run > duration * STATE_LEAK_FACTOR {
+        println!(
+            "{part_str}: {ANSI_BOLD}{STATE_LEAK_WARNING}{ANSI_RESET}, the first run took {first_run:.1?} \
+             but later runs {duration:.1?} on average. Compare with `cargo time {day} --fresh-process`."
+        );
+    }
+
+    let mut answers = Answers::read_from_file();
+    let submission = result.as_ref().and_then(Answer::submission);
+    answers.record_result(day, part, submission.clone());
+
+    if result.is_some() && submission.is_none() {
+        eprintln!(
+            "The letters of part {part} could not be read, they are neither stored nor submitted."
+        );
+    }
+
+    if let Some(submission) = submission {
+        if let Some(verdict) = submit_result(&submission, day, part) {
+            answers.record_submission(day, part, verdict.is_accepted());
+        }
+    }
+
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store answer: {e}");
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     With `--fresh-process`, every sample runs in a new process of the solution.
+///
+/// With the `alloc-stats` feature, the allocations of the first execution are counted as well.
+/// The duration of the first execution is returned last.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    part: Part,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<AllocStats>, Duration) {
+    if cfg!(feature = "alloc-stats") {
+        alloc_stats::reset();
+    }
+
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -39,15 +104,24 @@ fn run_timed<I: Copy, T>(
     };
     let base_time = timer.elapsed();
 
+    let alloc_stats = cfg!(feature = "alloc-stats").then(alloc_stats::snapshot);
+
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        recorder::pause();
+        let run = if std::env::args().any(|x| x == "--fresh-process") {
+            bench_fresh(part, &base_time)
+        } else {
+            bench(func, input, &base_time)
+        };
+        recorder::resume();
+        run
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, alloc_stats, base_time)
 }
 
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -74,6 +148,71 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     )
 }
 
+/// Benches a part by starting a new process of the solution for every sample,
+/// so that state kept between calls, e.g. a global cache, cannot speed up later samples.
+fn bench_fresh(part: Part, base_time: &Duration) -> (Duration, u128) {
+    let mut stdout = stdout();
+
+    print!(" > {ANSI_ITALIC}benching in fresh processes{ANSI_RESET}");
+    let _ = stdout.flush();
+
+    let Ok(exe) = env::current_exe() else {
+        eprintln!("Could not find the solution binary to bench in fresh processes.");
+        return (*base_time, 1);
+    };
+
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 100);
+
+    let mut timers: Vec<Duration> = vec![];
+
+    for _ in 0..bench_iterations {
+        let output = Command::new(&exe)
+            .env(FRESH_SAMPLE_VAR, part.to_string())
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+
+        // solutions might print to stdout themselves, the duration is the last line.
+        let nanos = output.ok().and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.trim().parse::<u64>().ok())
+                .next_back()
+        });
+
+        match nanos {
+            Some(nanos) => timers.push(Duration::from_nanos(nanos)),
+            None => {
+                eprintln!("Failed to run a sample in a fresh process.");
+                break;
+            }
+        }
+    }
+
+    if timers.is_empty() {
+        return (*base_time, 1);
+    }
+
+    (
+        #[allow(clippy::cast_possible_truncation)]
+        Duration::from_nanos(average_duration(&timers) as u64),
+        timers.len() as u128,
+    )
+}
+
+/// Runs a single sample for `--fresh-process` and prints its duration in nanoseconds.
+/// Parts other than the sampled one are skipped, and no answers are recorded.
+fn run_fresh_sample<I: Copy, T>(func: impl Fn(I) -> T, input: I, part: Part, sample_part: &str) {
+    if part.to_string() != sample_part {
+        return;
+    }
+
+    let timer = Instant::now();
+    black_box(func(black_box(input)));
+    println!("{}", timer.elapsed().as_nanos());
+}
+
 fn average_duration(numbers: &[Duration]) -> u128 {
     numbers
         .iter()
@@ -90,27 +229,29 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+            // drawings are shown below their letters, other multi-line answers below a marker.
+            let (header, body) = match result.drawing() {
+                Some(drawing) => {
+                    let text = result.submission().unwrap_or_else(|| "?".into());
+                    (format!("{ANSI_BOLD}{text}{ANSI_RESET} ▼"), Some(drawing))
                 }
+                None if result.to_string().contains('\n') => ("▼".into(), Some(result.to_string())),
+                None => (format!("{ANSI_BOLD}{result}{ANSI_RESET}"), None),
+            };
+
+            let str = format!("{part}: {header}{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
+                print!("\r");
+                println!("{str}");
+                if let Some(body) = body {
+                    println!("{body}");
                 }
             }
         }
@@ -128,11 +269,10 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  3. the answer is not known to be wrong from earlier submissions.
+///
+/// With `--dry-run`, the answer is checked but not sent. Returns the verdict of the server.
+fn submit_result(result: &str, day: Day, part: Part) -> Option<Verdict> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -146,20 +286,70 @@ fn submit_result<T: Display>(
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Some(part_arg) = args.get(part_index) else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
 
+    let part_submit = match part_arg.parse::<Part>() {
+        Ok(part_submit) => part_submit,
+        Err(e) => {
+            eprintln!("Invalid part \"{part_arg}\" for --submit: {e}.");
+            process::exit(1);
+        }
+    };
+
     if part_submit != part {
         return None;
     }
 
+    let mut history = Submissions::read_from_file();
+
+    if let Err(reason) = history.check(day, part, result) {
+        eprintln!("Not submitting \"{result}\": {reason}.");
+        print_history(&history, day, part);
+        return None;
+    }
+
+    if args.contains(&"--dry-run".into()) {
+        println!("Dry run: would submit \"{result}\" for day {day} part {part}.");
+        print_history(&history, day, part);
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let output = match aoc_cli::submit(day, part, result) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to submit: {e}");
+            return None;
+        }
+    };
+
+    let verdict = aoc_cli::verdict(&output);
+    history.record(day, part, result, verdict);
+    if let Err(e) = history.store_file() {
+        eprintln!("Failed to store submission: {e}");
+    }
+
+    Some(verdict)
+}
+
+fn print_history(history: &Submissions, day: Day, part: Part) {
+    let submissions: Vec<String> = history
+        .for_part(day, part)
+        .map(|submission| format!("  {submission}"))
+        .collect();
+
+    if submissions.is_empty() {
+        println!("No earlier submissions for day {day} part {part}.");
+    } else {
+        println!("Earlier submissions for day {day} part {part}:");
+        println!("{}", submissions.join("\n"));
+    }
 }
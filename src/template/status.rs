@@ -0,0 +1,169 @@
+/// Combines scaffolded solutions, stored answers and stored timings into a progress overview.
+use std::time::Duration;
+
+use crate::template::answers::{Answers, StoredAnswer};
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day, Part};
+
+/// The progress of a single part, from least to most complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PartStatus {
+    /// The day does not have a solution file yet.
+    NotScaffolded,
+    /// The solution exists but no result has been stored yet.
+    NotRun,
+    /// The solution returned `None`.
+    Unsolved,
+    /// The solution returned a value that has not been submitted.
+    Solved,
+    /// The value was submitted but has not been accepted.
+    Submitted,
+    /// The part has been accepted, i.e. it earned a star.
+    Accepted,
+}
+
+impl PartStatus {
+    fn from_answer(is_scaffolded: bool, answer: Option<&StoredAnswer>) -> Self {
+        if !is_scaffolded {
+            return Self::NotScaffolded;
+        }
+
+        match answer {
+            None => Self::NotRun,
+            Some(answer) if answer.accepted => Self::Accepted,
+            Some(answer) if answer.submitted => Self::Submitted,
+            Some(StoredAnswer { value: Some(_), .. }) => Self::Solved,
+            Some(StoredAnswer { value: None, .. }) => Self::Unsolved,
+        }
+    }
+
+    /// A single-width symbol used to draw the status.
+    pub fn symbol(self) -> char {
+        match self {
+            Self::NotScaffolded => '·',
+            Self::NotRun => '?',
+            Self::Unsolved => '✖',
+            Self::Solved => '○',
+            Self::Submitted => '☆',
+            Self::Accepted => '★',
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::NotScaffolded => "not scaffolded",
+            Self::NotRun => "not run",
+            Self::Unsolved => "unsolved",
+            Self::Solved => "solved",
+            Self::Submitted => "submitted",
+            Self::Accepted => "accepted",
+        }
+    }
+
+    pub fn all() -> [Self; 6] {
+        [
+            Self::Accepted,
+            Self::Submitted,
+            Self::Solved,
+            Self::Unsolved,
+            Self::NotRun,
+            Self::NotScaffolded,
+        ]
+    }
+}
+
+/// The progress of a single day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub is_scaffolded: bool,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
+    /// The stored benchmark time of both parts, formatted for display.
+    pub total_time: Option<String>,
+}
+
+/// Collect the status of every day of the event.
+pub fn collect(
+    answers: &Answers,
+    timings: &Timings,
+    is_scaffolded: impl Fn(Day) -> bool,
+) -> Vec<DayStatus> {
+    all_days()
+        .map(|day| {
+            let is_scaffolded = is_scaffolded(day);
+            let total_time = timings
+                .data
+                .iter()
+                .find(|t| t.day == day && t.total_nanos > 0_f64)
+                .map(|t| {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let duration = Duration::from_nanos(t.total_nanos as u64);
+                    format!("{duration:.1?}")
+                });
+
+            DayStatus {
+                day,
+                is_scaffolded,
+                part_1: PartStatus::from_answer(is_scaffolded, answers.get(day, Part::One)),
+                part_2: PartStatus::from_answer(is_scaffolded, answers.get(day, Part::Two)),
+                total_time,
+            }
+        })
+        .collect()
+}
+
+/// Count the stars earned over a set of days.
+pub fn count_stars(statuses: &[DayStatus]) -> usize {
+    statuses
+        .iter()
+        .flat_map(|s| [s.part_1, s.part_2])
+        .filter(|s| *s == PartStatus::Accepted)
+        .count()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, count_stars, PartStatus};
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            timings::{Timing, Timings},
+            Part,
+        },
+    };
+
+    #[test]
+    fn collects_day_statuses() {
+        let mut answers = Answers::default();
+        answers.record_result(day!(1), Part::One, Some("1".into()));
+        answers.record_submission(day!(1), Part::One, true);
+        answers.record_result(day!(1), Part::Two, None);
+        answers.record_result(day!(2), Part::One, Some("2".into()));
+        answers.record_submission(day!(2), Part::One, false);
+        answers.record_result(day!(2), Part::Two, Some("3".into()));
+
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some("1.0ms".into()),
+                part_2: None,
+                total_nanos: 1_500_000_f64,
+            }],
+        };
+
+        let statuses = collect(&answers, &timings, |day| day <= day!(3));
+
+        assert_eq!(statuses[0].part_1, PartStatus::Accepted);
+        assert_eq!(statuses[0].part_2, PartStatus::Unsolved);
+        assert_eq!(statuses[0].total_time, Some("1.5ms".into()));
+        assert_eq!(statuses[1].part_1, PartStatus::Submitted);
+        assert_eq!(statuses[1].part_2, PartStatus::Solved);
+        assert_eq!(statuses[2].part_1, PartStatus::NotRun);
+        assert_eq!(statuses[3].part_1, PartStatus::NotScaffolded);
+        assert_eq!(count_stars(&statuses), 1);
+    }
+}
@@ -0,0 +1,446 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Part};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// The response of the server to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The part was already solved, the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently, the answer was not checked.
+    TooRecent,
+    Unknown,
+}
+
+impl Verdict {
+    /// Reads the verdict from the message the server answers a submission with.
+    pub fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Self::Correct
+        } else if message.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else if message.contains("You gave an answer too recently") {
+            Self::TooRecent
+        } else if message.contains("your answer is too high") {
+            Self::TooHigh
+        } else if message.contains("your answer is too low") {
+            Self::TooLow
+        } else if message.contains("That's not the right answer") {
+            Self::Wrong
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether the part counts as solved after this verdict.
+    pub fn is_accepted(self) -> bool {
+        matches!(self, Self::Correct | Self::AlreadySolved)
+    }
+
+    /// Whether the server checked the answer and found it to be wrong.
+    pub fn is_rejected(self) -> bool {
+        matches!(self, Self::Wrong | Self::TooHigh | Self::TooLow)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::AlreadySolved => "already solved",
+            Self::TooRecent => "too recent",
+            Self::Unknown => "unknown",
+        };
+        write!(f, "{str}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "wrong" => Ok(Self::Wrong),
+            "too high" => Ok(Self::TooHigh),
+            "too low" => Ok(Self::TooLow),
+            "already solved" => Ok(Self::AlreadySolved),
+            "too recent" => Ok(Self::TooRecent),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(format!("unknown verdict \"{s}\"")),
+        }
+    }
+}
+
+/// Represents a single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Represents the history of all submissions.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+/// The reason an answer is not submitted.
+#[derive(Debug, PartialEq)]
+pub enum SubmissionRefused {
+    AlreadySolved { answer: String },
+    AlreadyRejected { verdict: Verdict },
+    TooHigh { bound: i128 },
+    TooLow { bound: i128 },
+}
+
+impl Display for SubmissionRefused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadySolved { answer } => {
+                write!(f, "the part was already solved with \"{answer}\"")
+            }
+            Self::AlreadyRejected { verdict } => {
+                write!(f, "the answer was already rejected as {verdict}")
+            }
+            Self::TooHigh { bound } => {
+                write!(f, "{bound} was already too high, answers must be lower")
+            }
+            Self::TooLow { bound } => {
+                write!(f, "{bound} was already too low, answers must be higher")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubmissionRefused {}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(SUBMISSIONS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Record a submission that was made just now.
+    pub fn record(&mut self, day: Day, part: Part, answer: &str, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+
+        self.data.push(Submission {
+            timestamp,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    /// All submissions of a part, oldest first.
+    pub fn for_part(&self, day: Day, part: Part) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// Checks an answer against the earlier submissions of its part. Refuses answers that are known to be
+    /// wrong: answers that were rejected before and numbers outside the known too high / too low bounds.
+    pub fn check(&self, day: Day, part: Part, answer: &str) -> Result<(), SubmissionRefused> {
+        if let Some(solved) = self
+            .for_part(day, part)
+            .find(|s| s.verdict == Verdict::Correct)
+        {
+            return Err(SubmissionRefused::AlreadySolved {
+                answer: solved.answer.clone(),
+            });
+        }
+
+        if let Some(rejected) = self
+            .for_part(day, part)
+            .find(|s| s.answer == answer && s.verdict.is_rejected())
+        {
+            return Err(SubmissionRefused::AlreadyRejected {
+                verdict: rejected.verdict,
+            });
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            self.for_part(day, part)
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(bound) = bound(Verdict::TooHigh)
+            .min()
+            .filter(|bound| value >= *bound)
+        {
+            return Err(SubmissionRefused::TooHigh { bound });
+        }
+        if let Some(bound) = bound(Verdict::TooLow).max().filter(|bound| value <= *bound) {
+            return Err(SubmissionRefused::TooLow { bound });
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}  {}  {}",
+            format_timestamp(self.timestamp),
+            self.answer,
+            self.verdict
+        )
+    }
+}
+
+/// Formats seconds since the unix epoch as a UTC date and time, e.g. `2024-12-13 05:04:11 UTC`.
+fn format_timestamp(timestamp: u64) -> String {
+    let (days, secs) = (timestamp / 86400, timestamp % 86400);
+
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::String(value.part.to_string()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")? as u64;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<String>())
+            .and_then(|part| Part::from_str(part).ok())
+            .ok_or("Expected submission.part to be a Part.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|verdict| Verdict::from_str(verdict).ok())
+            .ok_or("Expected submission.verdict to be a verdict.")?;
+
+        Ok(Submission {
+            timestamp,
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, SubmissionRefused, Submissions, Verdict};
+    use crate::{day, template::Part};
+
+    fn get_mock_history() -> Submissions {
+        let mut history = Submissions::default();
+        history.record(day!(3), Part::One, "500", Verdict::TooHigh);
+        history.record(day!(3), Part::One, "100", Verdict::TooLow);
+        history.record(day!(3), Part::One, "300", Verdict::Wrong);
+        history.record(day!(3), Part::Two, "7", Verdict::Correct);
+        history
+    }
+
+    #[test]
+    fn reads_verdicts() {
+        let message = "That's not the right answer; your answer is too high.  If you're stuck, ...";
+        assert_eq!(Verdict::from_message(message), Verdict::TooHigh);
+        assert_eq!(
+            Verdict::from_message("That's not the right answer.  If you're stuck, ..."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_message("That's the right answer!  You are one gold star closer"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_message("You gave an answer too recently; you have to wait"),
+            Verdict::TooRecent
+        );
+        assert!(Verdict::AlreadySolved.is_accepted());
+        assert!(!Verdict::TooRecent.is_rejected());
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let history = get_mock_history();
+        let check = |answer: &str| history.check(day!(3), Part::One, answer);
+
+        assert_eq!(check("200"), Ok(()));
+        assert_eq!(check("not a number"), Ok(()));
+        assert_eq!(
+            check("300"),
+            Err(SubmissionRefused::AlreadyRejected {
+                verdict: Verdict::Wrong
+            })
+        );
+        assert_eq!(
+            check("500"),
+            Err(SubmissionRefused::AlreadyRejected {
+                verdict: Verdict::TooHigh
+            })
+        );
+        assert_eq!(check("900"), Err(SubmissionRefused::TooHigh { bound: 500 }));
+        assert_eq!(check("99"), Err(SubmissionRefused::TooLow { bound: 100 }));
+        assert_eq!(
+            history.check(day!(3), Part::Two, "8"),
+            Err(SubmissionRefused::AlreadySolved { answer: "7".into() })
+        );
+        assert_eq!(history.check(day!(4), Part::One, "300"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = get_mock_history();
+        let json = tinyjson::JsonValue::from(history.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, history.data);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_timestamp(1_734_066_251), "2024-12-13 05:04:11 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00 UTC");
+    }
+}
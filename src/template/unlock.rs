@@ -0,0 +1,207 @@
+/// Works out when puzzles unlock and waits for them.
+/// Puzzles unlock at midnight in the timezone of the advent of code server (UTC-5).
+use std::{cmp, thread, time::Duration};
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::{Day, EVENT_DAYS};
+
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// A source of the current time. Injectable so that unlock logic can be tested offline.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The [`Clock`] backed by the system time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// The moment a puzzle becomes available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unlock {
+    pub day: Day,
+    pub at: DateTime<Utc>,
+}
+
+fn server_offset() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap()
+}
+
+/// Returns the day that is currently unlocked if it's part of the event, `None` otherwise.
+pub fn today(clock: &impl Clock) -> Option<Day> {
+    let today = clock.now().with_timezone(&server_offset());
+    if today.month() == 12 && today.day() <= u32::from(EVENT_DAYS) {
+        Day::new(u8::try_from(today.day()).ok()?)
+    } else {
+        None
+    }
+}
+
+/// Returns the next puzzle unlock, or `None` if the event of the current year is over.
+pub fn next_unlock(clock: &impl Clock) -> Option<Unlock> {
+    let offset = server_offset();
+    let now = clock.now().with_timezone(&offset);
+
+    let next_day = if now.month() == 12 { now.day() + 1 } else { 1 };
+    let day = Day::new(u8::try_from(next_day).ok()?)?;
+
+    let at = offset
+        .with_ymd_and_hms(now.year(), 12, next_day, 0, 0, 0)
+        .single()?
+        .with_timezone(&Utc);
+
+    Some(Unlock { day, at })
+}
+
+/// Blocks until `unlock` is reached, calling `on_tick` with the remaining time about once a second.
+pub fn wait_for_unlock(clock: &impl Clock, unlock: &Unlock, mut on_tick: impl FnMut(Duration)) {
+    while let Ok(remaining) = (unlock.at - clock.now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+        on_tick(remaining);
+        clock.sleep(cmp::min(remaining, Duration::from_secs(1)));
+    }
+}
+
+/// Calls `f` until it succeeds or `attempts` calls have failed.
+/// The delay between attempts starts at `delay` and doubles after every failure.
+pub fn retry_with_backoff<T, E>(
+    clock: &impl Clock,
+    attempts: u32,
+    mut delay: Duration,
+    mut f: impl FnMut(u32) -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match f(attempt) {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts => return Err(e),
+            Err(_) => {
+                clock.sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{cell::RefCell, time::Duration};
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{next_unlock, retry_with_backoff, today, wait_for_unlock, Clock};
+    use crate::day;
+
+    struct FakeClock {
+        now: RefCell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> Self {
+            Self {
+                now: RefCell::new(
+                    Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+                        .unwrap(),
+                ),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.borrow()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.now.borrow_mut() += duration;
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn today_uses_server_timezone() {
+        // 04:59 UTC on the 5th is still the 4th in UTC-5.
+        assert_eq!(today(&FakeClock::at(2024, 12, 5, 4, 59, 0)), Some(day!(4)));
+        assert_eq!(today(&FakeClock::at(2024, 12, 5, 5, 0, 0)), Some(day!(5)));
+        assert_eq!(today(&FakeClock::at(2024, 11, 30, 12, 0, 0)), None);
+        assert_eq!(today(&FakeClock::at(2024, 12, 26, 12, 0, 0)), None);
+    }
+
+    #[test]
+    fn next_unlock_before_event() {
+        let unlock = next_unlock(&FakeClock::at(2024, 11, 30, 12, 0, 0)).unwrap();
+        assert_eq!(unlock.day, day!(1));
+        assert_eq!(
+            unlock.at,
+            Utc.with_ymd_and_hms(2024, 12, 1, 5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn next_unlock_during_event() {
+        let unlock = next_unlock(&FakeClock::at(2024, 12, 5, 4, 59, 0)).unwrap();
+        assert_eq!(unlock.day, day!(5));
+        assert_eq!(
+            unlock.at,
+            Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn next_unlock_after_event() {
+        assert_eq!(next_unlock(&FakeClock::at(2024, 12, 25, 12, 0, 0)), None);
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock::at(2024, 12, 5, 4, 59, 57);
+        let unlock = next_unlock(&clock).unwrap();
+        let mut ticks = vec![];
+        wait_for_unlock(&clock, &unlock, |remaining| ticks.push(remaining.as_secs()));
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now(), unlock.at);
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(2024, 12, 1, 5, 0, 0);
+        let result = retry_with_backoff(&clock, 5, Duration::from_secs(1), |attempt| {
+            if attempt < 3 {
+                Err(attempt)
+            } else {
+                Ok(attempt)
+            }
+        });
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![Duration::from_secs(1), Duration::from_secs(2)]
+        );
+    }
+
+    #[test]
+    fn gives_up_after_attempts() {
+        let clock = FakeClock::at(2024, 12, 1, 5, 0, 0);
+        let result: Result<(), u32> = retry_with_backoff(&clock, 3, Duration::from_secs(1), Err);
+        assert_eq!(result, Err(3));
+        assert_eq!(clock.sleeps.borrow().len(), 2);
+    }
+}
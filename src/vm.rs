@@ -0,0 +1,502 @@
+//! The 3-bit computer of 2024 day 17, with a disassembler, a step tracer and a small assembler.
+//!
+//! A program is a list of 3-bit numbers that alternate between an opcode and its operand.
+//! Depending on the instruction, the operand is either a *literal* or a *combo* operand, where
+//! `0..=3` are literals, `4..=6` refer to the registers `A`, `B` and `C` and `7` is reserved.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    /// A program value at `pos` is not a 3-bit number.
+    InvalidOpcode { pos: usize, value: u8 },
+    /// The reserved combo operand 7 was used by the instruction at `pos`.
+    ReservedOperand { pos: usize },
+    /// The instruction at `pos` is the last value of the program and has no operand.
+    MissingOperand { pos: usize },
+    /// The program did not halt within the given number of steps.
+    StepLimit { steps: usize },
+    /// The input or the assembly could not be parsed.
+    Parse(String),
+}
+
+impl Error for VmError {}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::InvalidOpcode { pos, value } => {
+                write!(f, "invalid value {value} at position {pos}, expecting 0-7")
+            }
+            VmError::ReservedOperand { pos } => {
+                write!(f, "reserved combo operand 7 used at position {pos}")
+            }
+            VmError::MissingOperand { pos } => {
+                write!(f, "instruction at position {pos} is missing its operand")
+            }
+            VmError::StepLimit { steps } => write!(f, "program did not halt within {steps} steps"),
+            VmError::Parse(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+
+    /// Whether the operand is a combo operand, as opposed to a literal.
+    pub fn takes_combo(self) -> bool {
+        !matches!(self, Opcode::Bxl | Opcode::Jnz | Opcode::Bxc)
+    }
+
+    fn from_value(value: u8, pos: usize) -> Result<Self, VmError> {
+        Opcode::ALL
+            .get(usize::from(value))
+            .copied()
+            .ok_or(VmError::InvalidOpcode { pos, value })
+    }
+}
+
+/// A decoded instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: u8,
+}
+
+impl Instruction {
+    /// Decodes the instruction at `pos`, validating its operand.
+    pub fn decode(program: &[u8], pos: usize) -> Result<Self, VmError> {
+        let opcode = Opcode::from_value(program[pos], pos)?;
+        let operand = *program
+            .get(pos + 1)
+            .ok_or(VmError::MissingOperand { pos })?;
+
+        if operand > 7 {
+            return Err(VmError::InvalidOpcode {
+                pos: pos + 1,
+                value: operand,
+            });
+        }
+        if opcode.takes_combo() && operand == 7 {
+            return Err(VmError::ReservedOperand { pos });
+        }
+
+        Ok(Self { opcode, operand })
+    }
+}
+
+/// Disassembles an instruction, e.g. `adv 3` or `out A`.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mnemonic = self.opcode.mnemonic();
+        match self.opcode {
+            Opcode::Bxc => write!(f, "{mnemonic}"),
+            opcode if opcode.takes_combo() => match self.operand {
+                4 => write!(f, "{mnemonic} A"),
+                5 => write!(f, "{mnemonic} B"),
+                6 => write!(f, "{mnemonic} C"),
+                operand => write!(f, "{mnemonic} {operand}"),
+            },
+            _ => write!(f, "{mnemonic} {}", self.operand),
+        }
+    }
+}
+
+/// Disassembles a whole program into one line per instruction, prefixed with its position.
+pub fn disassemble(program: &[u8]) -> Result<String, VmError> {
+    let mut lines = Vec::new();
+    for pos in (0..program.len()).step_by(2) {
+        let instruction = Instruction::decode(program, pos)?;
+        lines.push(format!("{pos:02}: {instruction}"));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Assembles a program from text with one instruction per line, e.g. `adv 3` or `out A`.
+///
+/// Everything after a `;` is a comment. `bxc` may omit its ignored operand.
+pub fn assemble(source: &str) -> Result<Vec<u8>, VmError> {
+    let mut program = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line = line.split(';').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |message: &str| VmError::Parse(format!("line {}: {message}: `{line}`", i + 1));
+
+        let mut parts = line.split_whitespace();
+        let mnemonic = parts.next().unwrap_or_default().to_lowercase();
+        let opcode = Opcode::ALL
+            .into_iter()
+            .find(|op| op.mnemonic() == mnemonic)
+            .ok_or_else(|| error("unknown instruction"))?;
+
+        let operand = match (parts.next(), opcode) {
+            (None, Opcode::Bxc) => 0,
+            (None, _) => return Err(error("missing operand")),
+            (Some("A" | "a"), op) if op.takes_combo() => 4,
+            (Some("B" | "b"), op) if op.takes_combo() => 5,
+            (Some("C" | "c"), op) if op.takes_combo() => 6,
+            (Some(value), op) => match value.parse::<u8>() {
+                Ok(v) if v <= 3 || (!op.takes_combo() && v <= 7) => v,
+                _ => return Err(error("invalid operand")),
+            },
+        };
+
+        if parts.next().is_some() {
+            return Err(error("unexpected trailing input"));
+        }
+
+        program.extend([opcode as u8, operand]);
+    }
+
+    Ok(program)
+}
+
+/// The registers and the program counter before an instruction is executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceStep {
+    pub pos: usize,
+    pub instruction: Instruction,
+    pub registers: [u64; 3],
+}
+
+impl Display for TraceStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c] = self.registers;
+        write!(
+            f,
+            "{:02}: {:<6} A={a} B={b} C={c}",
+            self.pos,
+            self.instruction.to_string()
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Computer {
+    pub reg_a: u64,
+    pub reg_b: u64,
+    pub reg_c: u64,
+    pub program: Vec<u8>,
+    pub pos: usize,
+    pub output: Vec<u8>,
+}
+
+impl Computer {
+    pub fn new(program: Vec<u8>, [reg_a, reg_b, reg_c]: [u64; 3]) -> Self {
+        Self {
+            reg_a,
+            reg_b,
+            reg_c,
+            program,
+            ..Default::default()
+        }
+    }
+
+    pub fn registers(&self) -> [u64; 3] {
+        [self.reg_a, self.reg_b, self.reg_c]
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pos >= self.program.len()
+    }
+
+    fn combo(&self, operand: u8) -> u64 {
+        match operand {
+            4 => self.reg_a,
+            5 => self.reg_b,
+            6 => self.reg_c,
+            literal => u64::from(literal),
+        }
+    }
+
+    fn divide(&self, operand: u8) -> u64 {
+        u32::try_from(self.combo(operand))
+            .ok()
+            .and_then(|shift| self.reg_a.checked_shr(shift))
+            .unwrap_or(0)
+    }
+
+    /// Executes a single instruction and returns it, or `None` if the program has halted.
+    pub fn step(&mut self) -> Result<Option<Instruction>, VmError> {
+        if self.is_halted() {
+            return Ok(None);
+        }
+
+        let instruction = Instruction::decode(&self.program, self.pos)?;
+        let operand = instruction.operand;
+        self.pos += 2;
+
+        match instruction.opcode {
+            Opcode::Adv => self.reg_a = self.divide(operand),
+            Opcode::Bxl => self.reg_b ^= u64::from(operand),
+            Opcode::Bst => self.reg_b = self.combo(operand) % 8,
+            Opcode::Jnz => {
+                if self.reg_a != 0 {
+                    self.pos = usize::from(operand);
+                }
+            }
+            Opcode::Bxc => self.reg_b ^= self.reg_c,
+            #[allow(clippy::cast_possible_truncation)]
+            Opcode::Out => self.output.push((self.combo(operand) % 8) as u8),
+            Opcode::Bdv => self.reg_b = self.divide(operand),
+            Opcode::Cdv => self.reg_c = self.divide(operand),
+        }
+
+        Ok(Some(instruction))
+    }
+
+    /// Runs the program until it halts.
+    pub fn run(&mut self) -> Result<(), VmError> {
+        while self.step()?.is_some() {}
+        Ok(())
+    }
+
+    /// Runs the program until it halts or `max_steps` instructions have been executed.
+    pub fn run_with_limit(&mut self, max_steps: usize) -> Result<(), VmError> {
+        for _ in 0..max_steps {
+            if self.step()?.is_none() {
+                return Ok(());
+            }
+        }
+        if self.is_halted() {
+            Ok(())
+        } else {
+            Err(VmError::StepLimit { steps: max_steps })
+        }
+    }
+
+    /// Runs the program until it halts, calling `log` before every instruction.
+    pub fn trace(&mut self, mut log: impl FnMut(&TraceStep)) -> Result<(), VmError> {
+        while !self.is_halted() {
+            log(&TraceStep {
+                pos: self.pos,
+                instruction: Instruction::decode(&self.program, self.pos)?,
+                registers: self.registers(),
+            });
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Runs the program from the start with a different value in register A and returns its output.
+    /// Fails if the program does not halt within `max_steps` instructions.
+    pub fn output_for(&self, reg_a: u64, max_steps: usize) -> Result<Vec<u8>, VmError> {
+        let mut computer = Computer {
+            reg_a,
+            pos: 0,
+            output: vec![],
+            ..self.clone()
+        };
+        computer.run_with_limit(max_steps)?;
+        Ok(computer.output)
+    }
+
+    /// The output joined with commas, e.g. `4,6,3`.
+    pub fn output_string(&self) -> String {
+        self.output
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Parses the puzzle input format:
+///
+/// ```text
+/// Register A: 729
+/// Register B: 0
+/// Register C: 0
+///
+/// Program: 0,1,5,4,3,0
+/// ```
+impl FromStr for Computer {
+    type Err = VmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut registers = [0; 3];
+        let mut program = None;
+
+        for line in s.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| VmError::Parse(format!("expected `key: value`, got `{line}`")))?;
+            let value = value.trim();
+            let parse_error = || VmError::Parse(format!("invalid value in `{line}`"));
+
+            match key {
+                "Register A" => registers[0] = value.parse().map_err(|_| parse_error())?,
+                "Register B" => registers[1] = value.parse().map_err(|_| parse_error())?,
+                "Register C" => registers[2] = value.parse().map_err(|_| parse_error())?,
+                "Program" => {
+                    program = Some(
+                        value
+                            .split(',')
+                            .map(|v| v.trim().parse::<u8>().map_err(|_| parse_error()))
+                            .collect::<Result<Vec<_>, _>>()?,
+                    );
+                }
+                _ => return Err(VmError::Parse(format!("unknown key `{key}`"))),
+            }
+        }
+
+        let program = program.ok_or_else(|| VmError::Parse("missing program".into()))?;
+        Ok(Computer::new(program, registers))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{assemble, disassemble, Computer, VmError};
+
+    fn run(program: Vec<u8>, registers: [u64; 3]) -> Computer {
+        let mut computer = Computer::new(program, registers);
+        computer.run().unwrap();
+        computer
+    }
+
+    #[test]
+    fn runs_programs() {
+        assert_eq!(run(vec![2, 6], [0, 0, 9]).reg_b, 1);
+        assert_eq!(
+            run(vec![5, 0, 5, 1, 5, 4], [10, 0, 0]).output,
+            vec![0, 1, 2]
+        );
+
+        let computer = run(vec![0, 1, 5, 4, 3, 0], [2024, 0, 0]);
+        assert_eq!(computer.output_string(), "4,2,5,6,7,7,7,7,3,1,0");
+        assert_eq!(computer.reg_a, 0);
+
+        assert_eq!(run(vec![1, 7], [0, 29, 0]).reg_b, 26);
+        assert_eq!(run(vec![4, 0], [0, 2024, 43690]).reg_b, 44354);
+    }
+
+    #[test]
+    fn parses_input() {
+        let computer: Computer =
+            "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n"
+                .parse()
+                .unwrap();
+        assert_eq!(computer.registers(), [729, 0, 0]);
+        assert_eq!(computer.program, vec![0, 1, 5, 4, 3, 0]);
+        assert!("Register A: x\nProgram: 0,1".parse::<Computer>().is_err());
+        assert!("Register A: 1".parse::<Computer>().is_err());
+    }
+
+    #[test]
+    fn reports_invalid_programs() {
+        let mut computer = Computer::new(vec![0, 7], [1, 0, 0]);
+        assert_eq!(computer.run(), Err(VmError::ReservedOperand { pos: 0 }));
+
+        let mut computer = Computer::new(vec![5, 4, 8, 0], [1, 0, 0]);
+        assert_eq!(
+            computer.run(),
+            Err(VmError::InvalidOpcode { pos: 2, value: 8 })
+        );
+
+        let mut computer = Computer::new(vec![5, 4, 1], [1, 0, 0]);
+        assert_eq!(computer.run(), Err(VmError::MissingOperand { pos: 2 }));
+
+        // `bxl 7` is fine, its operand is a literal.
+        assert_eq!(run(vec![1, 7], [0, 0, 0]).reg_b, 7);
+
+        let mut computer = Computer::new(vec![3, 0], [1, 0, 0]);
+        assert_eq!(
+            computer.run_with_limit(10),
+            Err(VmError::StepLimit { steps: 10 })
+        );
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        assert_eq!(
+            disassemble(&[0, 3, 5, 4, 1, 7, 4, 2, 3, 0]).unwrap(),
+            "00: adv 3\n02: out A\n04: bxl 7\n06: bxc\n08: jnz 0"
+        );
+        assert!(disassemble(&[0, 7]).is_err());
+    }
+
+    #[test]
+    fn assembles_programs() {
+        let source = "
+            adv 3   ; shift A
+            out A
+            bxl 7
+            bxc
+            jnz 0
+        ";
+        let program = assemble(source).unwrap();
+        assert_eq!(program, vec![0, 3, 5, 4, 1, 7, 4, 0, 3, 0]);
+        let listing = disassemble(&program).unwrap();
+        let source: Vec<_> = listing.lines().map(|l| &l[4..]).collect();
+        assert_eq!(assemble(&source.join("\n")).unwrap(), program);
+
+        assert!(assemble("foo 1").is_err());
+        assert!(assemble("adv 7").is_err());
+        assert!(assemble("out").is_err());
+        assert!(assemble("adv 1 2").is_err());
+    }
+
+    #[test]
+    fn traces_programs() {
+        let mut computer = Computer::new(assemble("adv 1\nout A\njnz 0").unwrap(), [4, 0, 0]);
+        let mut log = Vec::new();
+        computer.trace(|step| log.push(step.to_string())).unwrap();
+        assert_eq!(
+            log,
+            vec![
+                "00: adv 1  A=4 B=0 C=0",
+                "02: out A  A=2 B=0 C=0",
+                "04: jnz 0  A=2 B=0 C=0",
+                "00: adv 1  A=2 B=0 C=0",
+                "02: out A  A=1 B=0 C=0",
+                "04: jnz 0  A=1 B=0 C=0",
+                "00: adv 1  A=1 B=0 C=0",
+                "02: out A  A=0 B=0 C=0",
+                "04: jnz 0  A=0 B=0 C=0",
+            ]
+        );
+        assert_eq!(computer.output_string(), "2,1,0");
+    }
+}